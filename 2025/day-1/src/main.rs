@@ -1,6 +1,6 @@
+use aoc_lib::dial::{Dial, Rotation};
 use aoc_lib::runner;
 use std::error::Error;
-use std::fmt::Display;
 
 const INPUT: &str = "./2025/day-1/input.txt";
 
@@ -11,119 +11,23 @@ fn main() -> Result<(), Box<dyn Error>> {
 }
 
 fn part_1(input: &str) -> usize {
-    input
-        .split('\n')
-        .map(|line| Rotation::from(line))
-        .fold(Counter::new(), |mut counter, rotation| {
-            if let Ok(r) = rotation {
-                counter.apply_rotation_1(&r);
-            }
-            counter
+    let mut dial = Dial::new(100, 50);
+    parse(input)
+        .map(|rotation| {
+            dial.rotate(rotation);
+            dial.position()
         })
-        .zero_count
+        .filter(|position| *position == 0)
+        .count()
 }
 
 fn part_2(input: &str) -> usize {
-    input
-        .split('\n')
-        .map(|line| Rotation::from(line))
-        .fold(Counter::new(), |mut counter, rotation| {
-            if let Ok(r) = rotation {
-                counter.apply_rotation_2(&r);
-            }
-            counter
-        })
-        .zero_count
-}
-
-#[derive(Debug)]
-enum Rotation {
-    Left(usize),
-    Right(usize),
-}
-
-impl Rotation {
-    fn from(string: &str) -> Result<Self, Box<dyn Error>> {
-        let direction = string
-            .chars()
-            .nth(0)
-            .ok_or_else(|| error("No first character to parse"))?;
-        let amount: usize = string[1..].parse()?;
-        match direction {
-            'L' => Ok(Self::Left(amount)),
-            'R' => Ok(Self::Right(amount)),
-            _ => Err(error("Character must be either L or R")),
-        }
-    }
+    let mut dial = Dial::new(100, 50);
+    parse(input).map(|rotation| dial.rotate(rotation)).sum()
 }
 
-#[derive(Debug)]
-struct Counter {
-    position: usize,
-    zero_count: usize,
-}
-
-impl Counter {
-    fn new() -> Self {
-        Counter {
-            position: 50,
-            zero_count: 0,
-        }
-    }
-
-    fn apply_rotation_1(&mut self, rotation: &Rotation) {
-        let new = match rotation {
-            Rotation::Left(amount) => {
-                let difference = amount % 100;
-                (100 + self.position - difference) % 100
-            }
-            Rotation::Right(amount) => (self.position + amount) % 100,
-        };
-        if new == 0 {
-            self.zero_count += 1;
-        }
-        self.position = new;
-    }
-
-    fn apply_rotation_2(&mut self, rotation: &Rotation) {
-        let new = match rotation {
-            Rotation::Left(amount) => {
-                self.zero_count += amount / 100;
-                let difference = amount % 100;
-                let new = (100 + self.position - difference) % 100;
-                if (new > self.position && self.position != 0) || new == 0 {
-                    self.zero_count += 1;
-                }
-                new
-            }
-            Rotation::Right(amount) => {
-                self.zero_count += amount / 100;
-                let new = (self.position + amount) % 100;
-                if new < self.position {
-                    self.zero_count += 1;
-                }
-                new
-            }
-        };
-        self.position = new;
-    }
-}
-
-#[derive(Debug)]
-struct RotationError {
-    reason: &'static str,
-}
-
-impl Display for RotationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.reason)
-    }
-}
-
-impl Error for RotationError {}
-
-fn error(msg: &'static str) -> Box<dyn Error> {
-    Box::new(RotationError { reason: msg })
+fn parse(input: &str) -> impl Iterator<Item = Rotation> {
+    input.split('\n').filter_map(|line| line.parse().ok())
 }
 
 #[cfg(test)]
@@ -1,9 +1,13 @@
+use aoc_lib::bitset::BitSet;
 use aoc_lib::runner;
 use std::collections::HashMap;
 use std::error::Error;
 
 const INPUT: &str = "./2025/day-11/input.txt";
 
+/// Devices every counted route in part 2 must pass through.
+const WAYPOINTS: [&str; 2] = ["fft", "dac"];
+
 fn main() -> Result<(), Box<dyn Error>> {
     runner::run("Part 1", INPUT, part_1)?;
     runner::run("Part 2", INPUT, part_2)?;
@@ -39,32 +43,41 @@ fn route_count(
 fn part_2(input: &str) -> usize {
     let graph = parse(input);
     let mut visited = HashMap::new();
-    route_with_stops_count(&graph, &mut visited, "svr").both
+    let all: BitSet = (0..WAYPOINTS.len()).collect();
+    route_with_stops_count(&graph, &mut visited, "svr")
+        .get(&all)
+        .copied()
+        .unwrap_or(0)
 }
 
+/// The number of routes from a device to the output, by the waypoints they pass through.
+type Counts = HashMap<BitSet, usize>;
+
 fn route_with_stops_count(
     graph: &HashMap<String, Vec<String>>,
     visited: &mut HashMap<String, Counts>,
     key: &str,
 ) -> Counts {
     let mut counts = Counts::new();
-    let bitmask = match key {
-        "fft" => 0b01,
-        "dac" => 0b10,
-        _ => 0b00,
-    };
+    let stop: BitSet = WAYPOINTS
+        .iter()
+        .position(|w| *w == key)
+        .into_iter()
+        .collect();
     let next = &graph[key];
     for next_key in next {
         if next_key == "out" {
-            counts.increment(bitmask);
-        } else if let Some(count) = visited.get(next_key) {
-            counts.add(count, bitmask);
-        } else {
-            let count = route_with_stops_count(graph, visited, next_key);
-            counts.add(&count, bitmask);
+            *counts.entry(stop).or_default() += 1;
+            continue;
+        }
+        if !visited.contains_key(next_key) {
+            route_with_stops_count(graph, visited, next_key);
+        }
+        for (stops, count) in &visited[next_key] {
+            *counts.entry(*stops | stop).or_default() += count;
         }
     }
-    visited.insert(key.to_owned(), counts);
+    visited.insert(key.to_owned(), counts.clone());
     counts
 }
 
@@ -79,58 +92,6 @@ fn parse(input: &str) -> HashMap<String, Vec<String>> {
         .collect()
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Counts {
-    neither: usize,
-    dac: usize,
-    fft: usize,
-    both: usize,
-}
-
-impl Counts {
-    fn new() -> Self {
-        Counts {
-            neither: 0,
-            dac: 0,
-            fft: 0,
-            both: 0,
-        }
-    }
-
-    fn increment(&mut self, bitmask: u8) {
-        match bitmask {
-            0b00 => self.neither += 1,
-            0b01 => self.fft += 1,
-            0b10 => self.dac += 1,
-            0b11 => self.both += 1,
-            _ => {}
-        }
-    }
-
-    fn add(&mut self, other: &Counts, bitmask: u8) {
-        match bitmask {
-            0b00 => {
-                self.neither += other.neither;
-                self.fft += other.fft;
-                self.dac += other.dac;
-                self.both += other.both;
-            }
-            0b01 => {
-                self.fft += other.neither + other.fft;
-                self.both += other.both + other.dac;
-            }
-            0b10 => {
-                self.dac += other.neither + other.dac;
-                self.both += other.both + other.fft;
-            }
-            0b11 => {
-                self.both += other.neither + other.dac + other.fft + other.both;
-            }
-            _ => {}
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
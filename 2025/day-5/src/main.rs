@@ -1,5 +1,5 @@
+use aoc_lib::intervals::IntervalSet;
 use aoc_lib::runner;
-use std::cmp::max;
 use std::error::Error;
 
 const INPUT: &str = "./2025/day-5/input.txt";
@@ -14,11 +14,11 @@ fn part_1(input: &str) -> usize {
     let (ranges, values) = input
         .split_once("\n\n")
         .expect("Should be a double new line to split input");
-    let merged = merged_ranges(ranges);
+    let fresh = parse_ranges(ranges);
     values
         .split('\n')
         .map(|line| line.parse().expect("Integer"))
-        .filter(|value| search(*value, &merged))
+        .filter(|value| fresh.contains(*value))
         .count()
 }
 
@@ -26,53 +26,18 @@ fn part_2(input: &str) -> usize {
     let (range_str, _) = input
         .split_once("\n\n")
         .expect("Should be a double new line to split input");
-    merged_ranges(range_str)
-        .iter()
-        .fold(0, |acc, (min, max)| acc + max - min + 1)
+    parse_ranges(range_str).total_len()
 }
 
-fn search(value: usize, ranges: &[(usize, usize)]) -> bool {
-    let (mut left, mut right) = (0, ranges.len());
-    while right - left > 0 {
-        let mid = left.midpoint(right);
-        let range = ranges[mid];
-        if value >= range.0 && value <= range.1 {
-            return true;
-        } else if value < range.0 {
-            right = mid;
-        } else {
-            left = mid + 1;
-        }
-    }
-    false
-}
-
-fn merged_ranges(ranges: &str) -> Vec<(usize, usize)> {
-    let mut initial: Vec<_> = ranges
+fn parse_ranges(ranges: &str) -> IntervalSet<usize> {
+    ranges
         .split('\n')
         .map(|line| {
-            line.split_once('-')
-                .map(|(min_str, max_str)| {
-                    (
-                        min_str.parse().expect("Integer"),
-                        max_str.parse().expect("Integer"),
-                    )
-                })
-                .expect("- delimiter expected")
+            let (min_str, max_str) = line.split_once('-').expect("- delimiter expected");
+            let min: usize = min_str.parse().expect("Integer");
+            min..=max_str.parse().expect("Integer")
         })
-        .collect();
-    initial.sort_unstable();
-    let mut merged: Vec<(usize, usize)> = Vec::new();
-    for range in initial {
-        if let Some(last) = merged.last_mut()
-            && last.1 >= range.0
-        {
-            last.1 = max(last.1, range.1);
-        } else {
-            merged.push(range);
-        }
-    }
-    merged
+        .collect()
 }
 
 #[cfg(test)]
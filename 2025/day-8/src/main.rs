@@ -1,4 +1,6 @@
+use aoc_lib::point::Point3;
 use aoc_lib::runner;
+use aoc_lib::union_find::DisjointSet;
 use std::cmp::Reverse;
 use std::error::Error;
 
@@ -22,9 +24,9 @@ fn part_2(input: &str) -> usize {
             let closest = vectors
                 .iter()
                 .filter(|conn| vector != *conn)
-                .min_by_key(|v| vector.distance_squared(v))
+                .min_by_key(|v| vector.distance_squared(**v))
                 .expect("Should be an element");
-            let distance = vector.distance_squared(closest);
+            let distance = vector.distance_squared(*closest);
             (closest.x * vector.x, distance)
         })
         .max_by_key(|(_, a)| *a)
@@ -41,7 +43,7 @@ fn part_1_parameterized(input: &str, num_connections: usize) -> usize {
             vectors[i + 1..]
                 .iter()
                 .enumerate()
-                .map(move |(j, conn)| (i, j + i + 1, vector.distance_squared(conn)))
+                .map(move |(j, conn)| (i, j + i + 1, vector.distance_squared(*conn)))
         })
         .collect();
     connections.sort_unstable_by_key(|(_, _, a)| *a);
@@ -54,87 +56,13 @@ fn part_1_parameterized(input: &str, num_connections: usize) -> usize {
     sizes.iter().take(3).product()
 }
 
-fn parse_vectors(input: &str) -> Vec<Vec3> {
+fn parse_vectors(input: &str) -> Vec<Point3<usize>> {
     input
         .split('\n')
-        .map(|line| {
-            let (x, rest) = line.split_once(',').expect("Comma separated digits");
-            let (y, z) = rest.split_once(',').expect("Comma separated digits");
-            Vec3::new(
-                x.parse().expect("Number"),
-                y.parse().expect("Number"),
-                z.parse().expect("Number"),
-            )
-        })
+        .map(|line| line.parse().expect("Comma separated digits"))
         .collect()
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-struct Vec3 {
-    x: usize,
-    y: usize,
-    z: usize,
-}
-
-impl Vec3 {
-    fn new(x: usize, y: usize, z: usize) -> Self {
-        Vec3 { x, y, z }
-    }
-
-    fn distance_squared(&self, other: &Vec3) -> usize {
-        self.x.abs_diff(other.x).pow(2)
-            + self.y.abs_diff(other.y).pow(2)
-            + self.z.abs_diff(other.z).pow(2)
-    }
-}
-
-#[derive(Debug)]
-struct DisjointSet {
-    parents: Vec<(usize, usize)>,
-    num_roots: usize,
-}
-
-impl DisjointSet {
-    fn new(size: usize) -> Self {
-        DisjointSet {
-            parents: (0..size).map(|i| (i, 1)).collect(),
-            num_roots: size,
-        }
-    }
-
-    fn find(&mut self, item: usize) -> usize {
-        if self.parents[item].0 != item {
-            self.parents[item].0 = self.find(self.parents[item].0);
-        }
-        self.parents[item].0
-    }
-
-    fn union(&mut self, a: usize, b: usize) -> bool {
-        let (root_a, root_b) = (self.find(a), self.find(b));
-        if root_a == root_b {
-            return false;
-        }
-
-        let (new_root, merged) = if self.parents[root_a].1 > self.parents[root_b].1 {
-            (root_a, root_b)
-        } else {
-            (root_b, root_a)
-        };
-
-        self.parents[merged].0 = self.parents[new_root].0;
-        self.parents[new_root].1 += self.parents[merged].1;
-        self.num_roots -= 1;
-        true
-    }
-
-    fn sizes(&self) -> impl Iterator<Item = usize> {
-        self.parents
-            .iter()
-            .enumerate()
-            .filter_map(|(i, (root, size))| (i == *root).then_some(*size))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
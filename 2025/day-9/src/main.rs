@@ -1,3 +1,4 @@
+use aoc_lib::point::Point2;
 use aoc_lib::runner;
 use std::{cmp::Reverse, error::Error};
 
@@ -33,36 +34,17 @@ fn part_2(input: &str) -> usize {
         .map_or_else(|| 0, |r| r.area)
 }
 
+/// Corners with their coordinates doubled, so the rays in [`Rect::in_perimeter`] can run between
+/// tiles on odd coordinates.
 fn parse(input: &str) -> impl Iterator<Item = Point> {
-    input.split('\n').map(Point::from)
+    input
+        .split('\n')
+        .map(|line| line.parse::<Point>().expect("Comma separated point") * 2)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-struct Point {
-    x: f64,
-    y: f64,
-}
-
-impl Point {
-    fn new(x: f64, y: f64) -> Self {
-        Point { x, y }
-    }
-}
-
-impl<T: AsRef<str>> From<T> for Point {
-    fn from(value: T) -> Self {
-        let (x_str, y_str) = value
-            .as_ref()
-            .split_once(',')
-            .expect("Comma separated point");
-        Point {
-            x: x_str.parse().expect("Should be a number"),
-            y: y_str.parse().expect("Should be a number"),
-        }
-    }
-}
+type Point = Point2<i64>;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Line {
     start: Point,
     end: Point,
@@ -71,7 +53,7 @@ struct Line {
 
 impl Line {
     fn new(start: Point, end: Point) -> Self {
-        let x_aligned = (start.y - end.y).abs() < 0.25;
+        let x_aligned = start.y == end.y;
         Line {
             start,
             end,
@@ -84,11 +66,9 @@ impl Line {
             return true;
         }
         if self.x_aligned {
-            (point.y - self.start.y).abs() < 0.25
-                && ((self.start.x <= point.x) ^ (self.end.x <= point.x))
+            point.y == self.start.y && ((self.start.x <= point.x) ^ (self.end.x <= point.x))
         } else {
-            (point.x - self.start.x).abs() < 0.25
-                && ((self.start.y <= point.y) ^ (self.end.y <= point.y))
+            point.x == self.start.x && ((self.start.y <= point.y) ^ (self.end.y <= point.y))
         }
     }
 
@@ -130,7 +110,8 @@ struct Rect {
 
 impl Rect {
     fn new(corner_a: Point, corner_b: Point) -> Self {
-        let area = ((corner_a.x - corner_b.x).abs() + 1.) * ((corner_a.y - corner_b.y).abs() + 1.);
+        let side = |a: i64, b: i64| usize::try_from(a.abs_diff(b) / 2 + 1).expect("Should fit");
+        let area = side(corner_a.x, corner_b.x) * side(corner_a.y, corner_b.y);
         let points = [
             corner_a,
             Point::new(corner_a.x, corner_b.y),
@@ -143,11 +124,10 @@ impl Rect {
             Line::new(points[2], points[3]),
             Line::new(points[3], points[0]),
         ];
-        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
         Rect {
             points,
             lines,
-            area: area as usize,
+            area,
         }
     }
 
@@ -155,11 +135,11 @@ impl Rect {
         if !self.points.iter().all(|point| {
             perimeter.iter().any(|l| l.contains(point))
                 || [
-                    Point::new(point.x - 0.5, point.y),
-                    Point::new(point.x + 0.5, point.y),
+                    Point::new(point.x - 1, point.y),
+                    Point::new(point.x + 1, point.y),
                 ]
                 .iter()
-                .map(|p| Line::new(Point::new(p.x, 0.), *p))
+                .map(|p| Line::new(Point::new(p.x, 0), *p))
                 .any(|ray| perimeter.iter().filter(|l| l.intersects(&ray)).count() % 2 == 1)
         }) {
             return false;
@@ -0,0 +1,391 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+
+const WORD: usize = u64::BITS as usize;
+
+/// The set bits of some words, in order.
+fn ones(words: &[u64]) -> impl Iterator<Item = usize> {
+    words.iter().enumerate().flat_map(|(i, word)| {
+        std::iter::successors(Some(*word).filter(|w| *w != 0), |w| {
+            Some(w & (w - 1)).filter(|rest| *rest != 0)
+        })
+        .map(move |w| i * WORD + w.trailing_zeros() as usize)
+    })
+}
+
+fn count(words: &[u64]) -> usize {
+    words.iter().map(|w| w.count_ones() as usize).sum()
+}
+
+/// A set of small integers kept inline in `WORDS` words, so it holds `0` up to `64 * WORDS - 1`
+/// and can be copied and hashed cheaply.
+///
+/// ```
+/// use aoc_lib::bitset::BitSet;
+///
+/// let mut lights: BitSet = [1, 2].into_iter().collect();
+/// let button: BitSet = [0, 2].into_iter().collect();
+/// lights ^= button;
+/// assert_eq!(vec![0, 1], lights.iter().collect::<Vec<_>>());
+/// assert_eq!(1, (lights & button).len());
+/// assert!(BitSet::<2>::new().insert(100));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet<const WORDS: usize = 1> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        BitSet { words: [0; WORDS] }
+    }
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    /// One more than the largest value the set can hold.
+    pub const CAPACITY: usize = WORDS * WORD;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && self.words[value / WORD] >> (value % WORD) & 1 == 1
+    }
+
+    /// Add a value, returning whether it was new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value does not fit in the set.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(
+            value < Self::CAPACITY,
+            "{value} should be below {}",
+            Self::CAPACITY
+        );
+        let added = !self.contains(value);
+        self.words[value / WORD] |= 1 << (value % WORD);
+        added
+    }
+
+    /// Take a value out, returning whether it was there.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let removed = self.contains(value);
+        if removed {
+            self.words[value / WORD] &= !(1 << (value % WORD));
+        }
+        removed
+    }
+
+    /// Add a value if it is missing, or take it out if it is there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value does not fit in the set.
+    pub fn toggle(&mut self, value: usize) {
+        if !self.remove(value) {
+            self.insert(value);
+        }
+    }
+
+    /// The number of values, by counting bits.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        count(&self.words)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .all(|(a, b)| a & !b == 0)
+    }
+
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+    }
+
+    /// Every value in the set, smallest first.
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        ones(&self.words)
+    }
+}
+
+/// A set of integers kept on the heap, which grows to hold whatever is inserted.
+///
+/// ```
+/// use aoc_lib::bitset::DynBitSet;
+///
+/// let mut seen: DynBitSet = [3, 500].into_iter().collect();
+/// let more: DynBitSet = [3, 4].into_iter().collect();
+/// seen |= &more;
+/// assert_eq!(vec![3, 4, 500], seen.iter().collect::<Vec<_>>());
+/// assert_eq!(vec![500], (&seen - &more).iter().collect::<Vec<_>>());
+/// seen.remove(500);
+/// assert_eq!(more, seen);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DynBitSet {
+    /// Never ends in a zero word, so equal sets are always stored the same way.
+    words: Vec<u64>,
+}
+
+impl DynBitSet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / WORD)
+            .is_some_and(|w| w >> (value % WORD) & 1 == 1)
+    }
+
+    /// Add a value, returning whether it was new.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let added = !self.contains(value);
+        let word = value / WORD;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (value % WORD);
+        added
+    }
+
+    /// Take a value out, returning whether it was there.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let removed = self.contains(value);
+        if removed {
+            self.words[value / WORD] &= !(1 << (value % WORD));
+            self.trim();
+        }
+        removed
+    }
+
+    /// Add a value if it is missing, or take it out if it is there.
+    pub fn toggle(&mut self, value: usize) {
+        if !self.remove(value) {
+            self.insert(value);
+        }
+    }
+
+    /// The number of values, by counting bits.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        count(&self.words)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words.len() <= other.words.len()
+            && self
+                .words
+                .iter()
+                .zip(&other.words)
+                .all(|(a, b)| a & !b == 0)
+    }
+
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+    }
+
+    /// Every value in the set, smallest first.
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        ones(&self.words)
+    }
+
+    /// Combine with another set word by word, treating missing words as zero.
+    fn combine(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (i, word) in self.words.iter_mut().enumerate() {
+            *word = op(*word, other.words.get(i).copied().unwrap_or(0));
+        }
+        self.trim();
+    }
+}
+
+macro_rules! operators {
+    ($($op:ident $method:ident $assign:ident $assign_method:ident $f:expr;)*) => {$(
+        impl<const WORDS: usize> $assign for BitSet<WORDS> {
+            fn $assign_method(&mut self, other: Self) {
+                for (word, other_word) in self.words.iter_mut().zip(other.words) {
+                    *word = $f(*word, other_word);
+                }
+            }
+        }
+
+        impl<const WORDS: usize> $op for BitSet<WORDS> {
+            type Output = Self;
+
+            fn $method(mut self, other: Self) -> Self {
+                self.$assign_method(other);
+                self
+            }
+        }
+
+        impl $assign<&DynBitSet> for DynBitSet {
+            fn $assign_method(&mut self, other: &DynBitSet) {
+                self.combine(other, $f);
+            }
+        }
+
+        impl $op for &DynBitSet {
+            type Output = DynBitSet;
+
+            fn $method(self, other: &DynBitSet) -> DynBitSet {
+                let mut result = self.clone();
+                result.$assign_method(other);
+                result
+            }
+        }
+    )*};
+}
+
+operators! {
+    BitOr bitor BitOrAssign bitor_assign |a: u64, b: u64| a | b;
+    BitAnd bitand BitAndAssign bitand_assign |a: u64, b: u64| a & b;
+    BitXor bitxor BitXorAssign bitxor_assign |a: u64, b: u64| a ^ b;
+    Sub sub SubAssign sub_assign |a: u64, b: u64| a & !b;
+}
+
+impl<const WORDS: usize> Extend<usize> for BitSet<WORDS> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, values: I) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(values: I) -> Self {
+        let mut set = Self::new();
+        set.extend(values);
+        set
+    }
+}
+
+impl Extend<usize> for DynBitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, values: I) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+impl FromIterator<usize> for DynBitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(values: I) -> Self {
+        let mut set = Self::new();
+        set.extend(values);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn inline_sets_span_words() {
+        let mut set = BitSet::<3>::new();
+        assert_eq!(192, BitSet::<3>::CAPACITY);
+        assert!(set.insert(0) && set.insert(64) && set.insert(191));
+        assert!(!set.insert(64));
+        set.toggle(0);
+        set.toggle(130);
+        assert_eq!(vec![64, 130, 191], set.iter().collect::<Vec<_>>());
+        assert!(!set.contains(500));
+        assert!(!set.remove(500));
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "64 should be below 64")]
+    fn inline_sets_are_bounded() {
+        BitSet::<1>::new().insert(64);
+    }
+
+    #[test]
+    fn dynamic_sets_compare_by_value() {
+        let mut grown: DynBitSet = [1, 1000].into_iter().collect();
+        grown.remove(1000);
+        assert_eq!(DynBitSet::from_iter([1]), grown);
+        let mut xored = grown.clone();
+        xored ^= &grown;
+        assert_eq!(DynBitSet::new(), xored);
+        assert!(xored.is_empty());
+    }
+
+    fn values() -> impl Strategy<Value = BTreeSet<usize>> {
+        prop::collection::btree_set(0..192_usize, 0..40)
+    }
+
+    proptest! {
+        #[test]
+        fn matches_a_btree_set(a in values(), b in values()) {
+            let (inline_a, inline_b): (BitSet<3>, BitSet<3>) =
+                (a.iter().copied().collect(), b.iter().copied().collect());
+            let (dyn_a, dyn_b): (DynBitSet, DynBitSet) =
+                (a.iter().copied().collect(), b.iter().copied().collect());
+            let expected = [
+                a.union(&b).copied().collect::<Vec<_>>(),
+                a.intersection(&b).copied().collect(),
+                a.symmetric_difference(&b).copied().collect(),
+                a.difference(&b).copied().collect(),
+            ];
+            let inline = [
+                inline_a | inline_b,
+                inline_a & inline_b,
+                inline_a ^ inline_b,
+                inline_a - inline_b,
+            ];
+            let dynamic = [&dyn_a | &dyn_b, &dyn_a & &dyn_b, &dyn_a ^ &dyn_b, &dyn_a - &dyn_b];
+            for ((values, fixed), grown) in expected.iter().zip(inline).zip(dynamic) {
+                prop_assert_eq!(values, &fixed.iter().collect::<Vec<_>>());
+                prop_assert_eq!(values, &grown.iter().collect::<Vec<_>>());
+                prop_assert_eq!(values.len(), fixed.len());
+                prop_assert_eq!(values.len(), grown.len());
+                prop_assert_eq!(grown, values.iter().copied().collect::<DynBitSet>());
+            }
+            prop_assert_eq!(a.is_subset(&b), inline_a.is_subset(&inline_b));
+            prop_assert_eq!(a.is_subset(&b), dyn_a.is_subset(&dyn_b));
+            prop_assert_eq!(a.is_disjoint(&b), inline_a.is_disjoint(&inline_b));
+            prop_assert_eq!(a.is_disjoint(&b), dyn_a.is_disjoint(&dyn_b));
+        }
+    }
+}
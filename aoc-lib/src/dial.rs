@@ -0,0 +1,173 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A dial with positions `0` to `size - 1`, which wraps around in either direction.
+///
+/// ```
+/// use aoc_lib::dial::{Dial, Rotation};
+///
+/// let mut dial = Dial::new(100, 50);
+/// assert_eq!(1, dial.rotate("L68".parse().unwrap()));
+/// assert_eq!(82, dial.position());
+/// assert_eq!(0, dial.rotate(Rotation::Right(10)));
+/// assert_eq!(10, dial.rotate(Rotation::Left(1000)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dial {
+    size: usize,
+    position: usize,
+}
+
+/// A turn of a dial, anticlockwise towards lower numbers or clockwise towards higher ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    Left(usize),
+    Right(usize),
+}
+
+impl Dial {
+    /// A dial of `size` positions, starting at `position` wrapped onto the dial.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dial has no positions.
+    #[must_use]
+    pub fn new(size: usize, position: usize) -> Self {
+        assert!(size > 0, "Dial should have at least one position");
+        Dial {
+            size,
+            position: position % size,
+        }
+    }
+
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Turn the dial, returning how many times it pointed at zero along the way, including where
+    /// it stops but not where it started.
+    pub fn rotate(&mut self, rotation: Rotation) -> usize {
+        let (size, position) = (self.size, self.position);
+        match rotation {
+            Rotation::Left(amount) => {
+                self.position = (position + size - amount % size) % size;
+                match position {
+                    0 => amount / size,
+                    _ if amount >= position => (amount - position) / size + 1,
+                    _ => 0,
+                }
+            }
+            Rotation::Right(amount) => {
+                // Compared against the distance to zero rather than added, so any amount is fine.
+                let turn = amount % size;
+                let wraps = turn >= size - position;
+                self.position = if wraps {
+                    turn - (size - position)
+                } else {
+                    position + turn
+                };
+                amount / size + usize::from(wraps)
+            }
+        }
+    }
+}
+
+/// A rotation was not `L` or `R` followed by a number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRotationError {
+    input: String,
+}
+
+impl Display for ParseRotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid rotation: {}", self.input)
+    }
+}
+
+impl Error for ParseRotationError {}
+
+/// Parse `L<amount>` or `R<amount>`.
+impl FromStr for Rotation {
+    type Err = ParseRotationError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseRotationError {
+            input: input.to_owned(),
+        };
+        let (direction, amount) = input.split_at_checked(1).ok_or_else(invalid)?;
+        let amount = amount.parse().map_err(|_| invalid())?;
+        match direction {
+            "L" => Ok(Rotation::Left(amount)),
+            "R" => Ok(Rotation::Right(amount)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Count zeros one click at a time.
+    fn brute_force(size: usize, position: usize, rotation: Rotation) -> (usize, usize) {
+        let mut zeros = 0;
+        let mut current = position;
+        let (amount, step) = match rotation {
+            Rotation::Left(amount) => (amount, size - 1),
+            Rotation::Right(amount) => (amount, 1),
+        };
+        for _ in 0..amount {
+            current = (current + step) % size;
+            if current == 0 {
+                zeros += 1;
+            }
+        }
+        (current, zeros)
+    }
+
+    #[test]
+    fn counts_zeros_like_turning_by_hand() {
+        for size in 1..6 {
+            for position in 0..size {
+                for amount in 0..15 {
+                    for rotation in [Rotation::Left(amount), Rotation::Right(amount)] {
+                        let mut dial = Dial::new(size, position);
+                        let zeros = dial.rotate(rotation);
+                        assert_eq!(
+                            brute_force(size, position, rotation),
+                            (dial.position(), zeros),
+                            "{rotation:?} from {position} of {size}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn turns_any_amount() {
+        let mut dial = Dial::new(100, 99);
+        assert_eq!(
+            usize::MAX / 100 + 1,
+            dial.rotate(Rotation::Right(usize::MAX))
+        );
+        assert_eq!((99 + usize::MAX % 100) % 100, dial.position());
+    }
+
+    #[test]
+    fn parses_rotations() {
+        assert_eq!(Ok(Rotation::Left(68)), "L68".parse());
+        assert_eq!(Ok(Rotation::Right(0)), "R0".parse());
+        "".parse::<Rotation>().expect_err("Empty");
+        "X5".parse::<Rotation>().expect_err("Not a direction");
+        "R".parse::<Rotation>().expect_err("No amount");
+        "é5".parse::<Rotation>().expect_err("Multibyte direction");
+    }
+}
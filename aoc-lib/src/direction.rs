@@ -0,0 +1,205 @@
+use crate::grid::Position;
+use crate::point::Point2;
+
+/// One of the four orthogonal compass directions, with north up the page.
+///
+/// ```
+/// use aoc_lib::direction::Direction4;
+/// use aoc_lib::point::Point2;
+///
+/// let facing = Direction4::from_char('^').unwrap();
+/// assert_eq!(Direction4::East, facing.turn_right());
+/// assert_eq!(Point2::new(5, 3), Point2::new(5, 2) + facing.opposite().delta());
+/// assert_eq!(Some((2, 3)), facing.step((3, 3)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// One of the eight compass directions, including diagonals.
+///
+/// ```
+/// use aoc_lib::direction::Direction8;
+/// use aoc_lib::point::Point2;
+///
+/// assert_eq!(Direction8::NorthEast, Direction8::North.turn_right());
+/// assert_eq!(Direction8::SouthWest, Direction8::NorthEast.opposite());
+/// assert_eq!(Point2::new(-1, -1), Direction8::NorthWest.delta());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction4 {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction4; 4] = [
+        Direction4::North,
+        Direction4::East,
+        Direction4::South,
+        Direction4::West,
+    ];
+
+    /// Read an arrow (`^>v<`), a step (`URDL`) or a compass point (`NESW`).
+    #[must_use]
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'U' | 'N' => Some(Direction4::North),
+            '>' | 'R' | 'E' => Some(Direction4::East),
+            'v' | 'D' | 'S' => Some(Direction4::South),
+            '<' | 'L' | 'W' => Some(Direction4::West),
+            _ => None,
+        }
+    }
+
+    /// The direction a quarter turn anticlockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// The direction a quarter turn clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The step as a vector, with `y` growing downwards like a
+    /// [`SparseGrid`](crate::sparse::SparseGrid).
+    #[must_use]
+    pub const fn delta(self) -> Point2<i64> {
+        Direction8::from_direction4(self).delta()
+    }
+
+    /// The neighbouring grid position in this direction, or `None` if it would be off the top or
+    /// left. The caller checks the other edges against the grid.
+    #[must_use]
+    pub const fn step(self, position: Position) -> Option<Position> {
+        Direction8::from_direction4(self).step(position)
+    }
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    const fn from_direction4(direction: Direction4) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+
+    /// The direction an eighth of a turn anticlockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// The direction an eighth of a turn clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The step as a vector, with `y` growing downwards like a
+    /// [`SparseGrid`](crate::sparse::SparseGrid).
+    #[must_use]
+    pub const fn delta(self) -> Point2<i64> {
+        match self {
+            Direction8::North => Point2::new(0, -1),
+            Direction8::NorthEast => Point2::new(1, -1),
+            Direction8::East => Point2::new(1, 0),
+            Direction8::SouthEast => Point2::new(1, 1),
+            Direction8::South => Point2::new(0, 1),
+            Direction8::SouthWest => Point2::new(-1, 1),
+            Direction8::West => Point2::new(-1, 0),
+            Direction8::NorthWest => Point2::new(-1, -1),
+        }
+    }
+
+    /// The neighbouring grid position in this direction, or `None` if it would be off the top or
+    /// left. The caller checks the other edges against the grid.
+    #[must_use]
+    pub const fn step(self, (row, column): Position) -> Option<Position> {
+        let Point2 { x: dx, y: dy } = self.delta();
+        // Deltas are all -1, 0 or 1, so the casts cannot truncate.
+        #[allow(clippy::cast_possible_truncation)]
+        match (
+            row.checked_add_signed(dy as isize),
+            column.checked_add_signed(dx as isize),
+        ) {
+            (Some(r), Some(c)) => Some((r, c)),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Direction8::from_direction4(direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_come_full_circle() {
+        for direction in Direction4::ALL {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(direction.opposite(), direction.turn_right().turn_right());
+            assert_eq!(direction.delta() * -1, direction.opposite().delta());
+        }
+        for direction in Direction8::ALL {
+            assert_eq!(direction, direction.turn_right().turn_left());
+            assert_eq!(
+                direction.opposite(),
+                (0..4).fold(direction, |d, _| d.turn_right())
+            );
+        }
+        assert_eq!(Direction4::North, Direction4::West.turn_right());
+        assert_eq!(Direction8::NorthWest, Direction8::North.turn_left());
+    }
+
+    #[test]
+    fn steps_around_grid_positions() {
+        assert_eq!(None, Direction4::North.step((0, 4)));
+        assert_eq!(None, Direction8::SouthWest.step((2, 0)));
+        assert_eq!(Some((3, 0)), Direction8::SouthWest.step((2, 1)));
+        assert_eq!(Some((0, 5)), Direction4::East.step((0, 4)));
+        assert_eq!(
+            Direction8::East,
+            Direction8::from(Direction4::from_char('R').expect("Should be a direction"))
+        );
+        assert_eq!(None, Direction4::from_char('x'));
+    }
+}
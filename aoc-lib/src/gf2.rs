@@ -0,0 +1,410 @@
+use crate::bitset::DynBitSet;
+use std::ops::BitXorAssign;
+
+/// A vector over GF(2), where adding is XOR, kept as the set of its ones so it can be any
+/// length.
+///
+/// ```
+/// use aoc_lib::gf2::BitVector;
+///
+/// let mut lights: BitVector = ".##.".chars().map(|c| c == '#').collect();
+/// lights ^= &[true, true, false, false].into_iter().collect();
+/// assert_eq!(vec![0, 2], lights.ones().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    ones: DynBitSet,
+    len: usize,
+}
+
+impl BitVector {
+    /// A vector of zeros.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        BitVector {
+            ones: DynBitSet::new(),
+            len,
+        }
+    }
+
+    /// A vector with ones at the values of a set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set holds anything past the end.
+    #[must_use]
+    pub fn from_set(ones: DynBitSet, len: usize) -> Self {
+        assert!(
+            ones.iter().all(|one| one < len),
+            "Ones should be within {len} bits"
+        );
+        BitVector { ones, len }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the index is past the end.
+    #[must_use]
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "Bit {index} should be in the vector");
+        self.ones.contains(index)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the index is past the end.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "Bit {index} should be in the vector");
+        if value {
+            self.ones.insert(index);
+        } else {
+            self.ones.remove(index);
+        }
+    }
+
+    /// The number of ones.
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.ones.len()
+    }
+
+    /// The index of every one, in order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> {
+        self.ones.iter()
+    }
+}
+
+/// Add vectors of the same length.
+impl BitXorAssign<&BitVector> for BitVector {
+    fn bitxor_assign(&mut self, other: &BitVector) {
+        assert_eq!(self.len, other.len, "Vectors should be the same length");
+        self.ones ^= &other.ones;
+    }
+}
+
+impl FromIterator<bool> for BitVector {
+    fn from_iter<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        let mut len = 0;
+        let ones = bits
+            .into_iter()
+            .inspect(|_| len += 1)
+            .enumerate()
+            .filter_map(|(i, bit)| bit.then_some(i))
+            .collect();
+        BitVector { ones, len }
+    }
+}
+
+/// A matrix over GF(2), stored as a bit vector per row.
+///
+/// Systems from toggling puzzles map straight on to it: a column per switch, a row per light,
+/// and a one wherever the switch toggles the light.
+///
+/// ```
+/// use aoc_lib::gf2::{BitMatrix, BitVector};
+///
+/// // The buttons (3) (1,3) (2) (2,3) (0,2) (0,1) as columns, each toggling some of four lights.
+/// let buttons = [vec![3], vec![1, 3], vec![2], vec![2, 3], vec![0, 2], vec![0, 1]];
+/// let mut matrix = BitMatrix::new(4, buttons.len());
+/// for (column, lights) in buttons.iter().enumerate() {
+///     for light in lights {
+///         matrix.set(*light, column, true);
+///     }
+/// }
+/// let target: BitVector = ".##.".chars().map(|c| c == '#').collect();
+/// let fewest = matrix.min_weight_solution(&target).unwrap();
+/// assert_eq!(2, fewest.count_ones());
+/// assert_eq!(4, matrix.solutions(&target).unwrap().count());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    rows: Vec<BitVector>,
+    columns: usize,
+}
+
+/// A matrix in reduced row echelon form, with the column of each row's leading one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rref {
+    matrix: BitMatrix,
+    pivots: Vec<usize>,
+}
+
+impl BitMatrix {
+    /// A matrix of zeros.
+    #[must_use]
+    pub fn new(rows: usize, columns: usize) -> Self {
+        BitMatrix {
+            rows: vec![BitVector::new(columns); rows],
+            columns,
+        }
+    }
+
+    /// A matrix from its rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows are not all `columns` long.
+    #[must_use]
+    pub fn from_rows(rows: Vec<BitVector>, columns: usize) -> Self {
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(columns, row.len(), "Row {i} should be {columns} wide");
+        }
+        BitMatrix { rows, columns }
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &BitVector {
+        &self.rows[row]
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        self.rows[row].get(column)
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, value: bool) {
+        self.rows[row].set(column, value);
+    }
+
+    /// Reduced row echelon form, by Gaussian elimination with XOR.
+    #[must_use]
+    pub fn rref(&self) -> Rref {
+        let mut matrix = self.clone();
+        let mut pivots = Vec::new();
+        for column in 0..self.columns {
+            let row = pivots.len();
+            let Some(found) = (row..self.rows()).find(|r| matrix.get(*r, column)) else {
+                continue;
+            };
+            matrix.rows.swap(row, found);
+            let pivot = matrix.rows[row].clone();
+            for (r, other) in matrix.rows.iter_mut().enumerate() {
+                if r != row && other.get(column) {
+                    *other ^= &pivot;
+                }
+            }
+            pivots.push(column);
+            if pivots.len() == self.rows() {
+                break;
+            }
+        }
+        Rref { matrix, pivots }
+    }
+
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        self.rref().rank()
+    }
+
+    /// A basis of the vectors `v` with `self * v = 0`.
+    #[must_use]
+    pub fn null_space(&self) -> Vec<BitVector> {
+        self.rref().null_space()
+    }
+
+    /// The reduced form of the matrix with `rhs` as an extra column, and whether `self * x = rhs`
+    /// has a solution.
+    fn reduce_with(&self, rhs: &BitVector) -> (Rref, bool) {
+        assert_eq!(self.rows(), rhs.len(), "Should be a value per row");
+        let rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                let mut ones = row.ones.clone();
+                if rhs.get(r) {
+                    ones.insert(self.columns);
+                }
+                BitVector::from_set(ones, self.columns + 1)
+            })
+            .collect();
+        let rref = BitMatrix::from_rows(rows, self.columns + 1).rref();
+        let solvable = rref.pivots.last() != Some(&self.columns);
+        (rref, solvable)
+    }
+
+    /// A solution to `self * x = rhs`, with every free variable zero, or `None` if there is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is not as tall as the matrix.
+    #[must_use]
+    pub fn solve(&self, rhs: &BitVector) -> Option<BitVector> {
+        let (rref, solvable) = self.reduce_with(rhs);
+        solvable.then(|| {
+            let mut solution = BitVector::new(self.columns);
+            for (row, column) in rref.pivots.iter().enumerate() {
+                solution.set(*column, rref.matrix.get(row, self.columns));
+            }
+            solution
+        })
+    }
+
+    /// Every solution to `self * x = rhs`, or `None` if there are none. There are two to the power
+    /// of the number of free variables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is not as tall as the matrix, or there are 64 or more free variables.
+    #[must_use]
+    pub fn solutions(&self, rhs: &BitVector) -> Option<impl Iterator<Item = BitVector> + use<>> {
+        let mut current = self.solve(rhs)?;
+        let basis = self.null_space();
+        assert!(basis.len() < 64, "Too many free variables to enumerate");
+        // Walk the combinations of the basis in Gray code order, so each step flips one vector.
+        Some((0..1_u64 << basis.len()).map(move |i| {
+            if i > 0 {
+                current ^= &basis[i.trailing_zeros() as usize];
+            }
+            current.clone()
+        }))
+    }
+
+    /// A solution to `self * x = rhs` with the fewest ones, or `None` if there is none. This tries
+    /// every solution, so takes time exponential in the number of free variables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is not as tall as the matrix, or there are 64 or more free variables.
+    #[must_use]
+    pub fn min_weight_solution(&self, rhs: &BitVector) -> Option<BitVector> {
+        self.solutions(rhs)?.min_by_key(BitVector::count_ones)
+    }
+}
+
+impl Rref {
+    /// The reduced matrix. Rows past the rank are all zero.
+    #[must_use]
+    pub fn matrix(&self) -> &BitMatrix {
+        &self.matrix
+    }
+
+    /// The pivot column of each non-zero row, in order.
+    #[must_use]
+    pub fn pivots(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// The columns without a pivot, whose variables can take either value.
+    #[must_use]
+    pub fn free_columns(&self) -> Vec<usize> {
+        (0..self.matrix.columns)
+            .filter(|c| !self.pivots.contains(c))
+            .collect()
+    }
+
+    /// A basis of the null space, with one vector per free column that sets it.
+    #[must_use]
+    pub fn null_space(&self) -> Vec<BitVector> {
+        self.free_columns()
+            .into_iter()
+            .map(|free| {
+                let mut vector = BitVector::new(self.matrix.columns);
+                vector.set(free, true);
+                for (row, pivot) in self.pivots.iter().enumerate() {
+                    vector.set(*pivot, self.matrix.get(row, free));
+                }
+                vector
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn multiply(matrix: &BitMatrix, vector: &BitVector) -> BitVector {
+        (0..matrix.rows())
+            .map(|r| vector.ones().filter(|c| matrix.get(r, *c)).count() % 2 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn solves_wide_systems() {
+        // Each of 100 switches toggles its own light and the next, so they chain across words.
+        let size = 100;
+        let mut matrix = BitMatrix::new(size, size);
+        for switch in 0..size {
+            matrix.set(switch, switch, true);
+            if switch + 1 < size {
+                matrix.set(switch + 1, switch, true);
+            }
+        }
+        assert_eq!(size, matrix.rank());
+        assert!(matrix.null_space().is_empty());
+        let target: BitVector = (0..size).map(|light| light == 70).collect();
+        let solution = matrix.solve(&target).expect("Should be solvable");
+        assert_eq!(target, multiply(&matrix, &solution));
+        assert_eq!(
+            (70..size).collect::<Vec<_>>(),
+            solution.ones().collect::<Vec<_>>()
+        );
+    }
+
+    /// A small system of lights and switches, with a target that often has no solution.
+    fn system() -> impl Strategy<Value = (BitMatrix, BitVector)> {
+        (2..=6usize, 1..=7usize).prop_flat_map(|(rows, columns)| {
+            let matrix = prop::collection::vec(prop::collection::vec(any::<bool>(), columns), rows)
+                .prop_map(move |rows| {
+                    BitMatrix::from_rows(
+                        rows.into_iter().map(BitVector::from_iter).collect(),
+                        columns,
+                    )
+                });
+            let target = prop::collection::vec(any::<bool>(), rows).prop_map(BitVector::from_iter);
+            (matrix, target)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn finds_the_lightest_solution((matrix, target) in system()) {
+            // Checked against trying every subset of columns.
+            let columns = matrix.columns();
+            let brute = (0..1_u32 << columns)
+                .map(|subset| {
+                    (0..columns)
+                        .map(|c| subset >> c & 1 == 1)
+                        .collect::<BitVector>()
+                })
+                .filter(|x| multiply(&matrix, x) == target)
+                .map(|x| x.count_ones())
+                .min();
+            let lightest = matrix.min_weight_solution(&target);
+            prop_assert_eq!(brute, lightest.as_ref().map(BitVector::count_ones));
+            if let Some(x) = lightest {
+                prop_assert_eq!(&target, &multiply(&matrix, &x));
+            }
+            for v in matrix.null_space() {
+                prop_assert_eq!(0, multiply(&matrix, &v).count_ones());
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+/// A position in a grid, as `(row, column)` from the top left.
+pub type Position = (usize, usize);
+
+/// Offsets to the orthogonal neighbours of a cell: up, right, down and left.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets to all eight neighbours of a cell, clockwise from up.
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// A dense rectangular grid, stored row by row in a single `Vec`.
+///
+/// ```
+/// use aoc_lib::grid::Grid;
+///
+/// let mut grid = Grid::parse("..@\n@.@", |c| c == '@');
+/// assert_eq!((3, 2), (grid.width(), grid.height()));
+/// assert!(grid[(1, 2)]);
+/// assert_eq!(3, grid.neighbours8((0, 1)).filter(|&p| grid[p]).count());
+/// grid[(0, 0)] = true;
+/// assert_eq!(Some((0, 0)), grid.find(&true));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to the same value.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parse a character map, mapping each character to a cell.
+    ///
+    /// Trailing blank lines are ignored, so inputs ending in a newline parse the same.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows are not all the same length.
+    #[must_use]
+    pub fn parse<F>(input: &str, mut cell: F) -> Self
+    where
+        F: FnMut(char) -> T,
+    {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = 0;
+        let mut height = 0;
+        for line in input.trim_end_matches(['\n', '\r']).lines() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let row_width = cells.len() - before;
+            if height == 0 {
+                width = row_width;
+            }
+            assert_eq!(width, row_width, "Row {height} should be {width} wide");
+            height += 1;
+        }
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    #[must_use]
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then_some(position.0 * self.width + position.1)
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The up, right, down and left neighbours of a position that are inside the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + use<T> {
+        self.offsets(position, &ORTHOGONAL)
+    }
+
+    /// All eight neighbours of a position that are inside the grid, including diagonals.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + use<T> {
+        self.offsets(position, &SURROUNDING)
+    }
+
+    fn offsets(
+        &self,
+        (row, column): Position,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + use<T> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(dr, dc)| {
+            let r = row.checked_add_signed(*dr).filter(|r| *r < height)?;
+            let c = column.checked_add_signed(*dc).filter(|c| *c < width)?;
+            Some((r, c))
+        })
+    }
+
+    /// The cells of a row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row is outside the grid.
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "Row {row} should be in the grid");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Every row, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Chunks of zero are not allowed, and a grid without columns has no cells to show anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of a column, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is outside the grid.
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "Column {column} should be in the grid");
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    /// The position of the first cell with a value, row by row.
+    #[must_use]
+    pub fn find(&self, value: &T) -> Option<Position>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find(|(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+
+    /// The positions of every cell with a value, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).expect("Position should be in the grid")
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .expect("Position should be in the grid")
+    }
+}
+
+/// Render the grid with each cell's `Display`, one line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#.#\n.S.\n#..\n";
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = Grid::parse(MAP, |c| c);
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!("#.#\n.S.\n#..", grid.to_string());
+        assert_eq!(Some((1, 1)), grid.find(&'S'));
+        assert_eq!(
+            vec![(0, 0), (0, 2), (2, 0)],
+            grid.find_all(&'#').collect::<Vec<_>>()
+        );
+
+        let empty = Grid::parse("", |c| c);
+        assert_eq!((0, 0), (empty.width(), empty.height()));
+        assert_eq!("", empty.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 1 should be 3 wide")]
+    fn rejects_ragged_rows() {
+        let _ = Grid::parse("...\n..", |c| c);
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 1), (1, 1), (1, 0)],
+            grid.neighbours8((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(5, grid.neighbours8((1, 1)).count());
+        assert_eq!(3, grid.neighbours4((1, 1)).count());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::parse("abc\ndef", |c| c);
+        assert_eq!(&['d', 'e', 'f'], grid.row(1));
+        assert_eq!(vec![&'b', &'e'], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(2, grid.rows().count());
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(None, grid.get((0, 3)));
+    }
+}
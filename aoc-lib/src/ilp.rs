@@ -0,0 +1,387 @@
+use crate::linalg::{Matrix, Rational};
+use std::ops::RangeInclusive;
+
+/// A system of linear equations over bounded integer variables, to be minimised under a linear
+/// cost.
+///
+/// The equations are reduced exactly, so every pivot variable is fixed by the free ones. Only the
+/// free variables are searched, by branch and bound over their ranges, which suits the few free
+/// variables that puzzle systems usually leave. Their ranges must be small enough to walk.
+///
+/// ```
+/// use aoc_lib::ilp::IntegerProgram;
+///
+/// // Fewest coins from 1, 5 and 10 making 27, with no more than one ten.
+/// let mut program = IntegerProgram::new(vec![0..=27, 0..=27, 0..=1]);
+/// program.constrain(&[1, 5, 10], 27);
+/// let best = program.minimise(&[1, 1, 1]).unwrap();
+/// assert_eq!((6, vec![2, 3, 1]), (best.cost, best.values));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegerProgram {
+    bounds: Vec<RangeInclusive<i64>>,
+    equations: Vec<Vec<i64>>,
+    constants: Vec<i64>,
+}
+
+/// The cheapest assignment found, with a value for every variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub cost: i64,
+    pub values: Vec<i64>,
+}
+
+/// A reduced equation scaled to integers: `scale * pivot = constant - coefficients . free`.
+#[derive(Debug)]
+struct Row {
+    pivot: usize,
+    scale: i128,
+    constant: i128,
+    coefficients: Vec<i128>,
+}
+
+/// The search over free variables, with everything kept as integers for speed.
+#[derive(Debug)]
+struct Search<'a> {
+    bounds: &'a [RangeInclusive<i64>],
+    free: Vec<usize>,
+    rows: Vec<Row>,
+    /// The cost is `(constant + cost_coefficients . free) / cost_scale`.
+    cost_scale: i128,
+    cost_coefficients: Vec<i128>,
+    /// The smallest and largest each row's sum can change by from the free variables at each depth
+    /// onwards, and the smallest the cost can be from there including its constant.
+    row_ranges: Vec<Vec<(i128, i128)>>,
+    cost_minimums: Vec<i128>,
+    values: Vec<i64>,
+    best: Option<(i128, Vec<i64>)>,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i128::try_from(a).expect("Common divisor should fit in an i128")
+}
+
+fn lcm(values: impl IntoIterator<Item = i128>) -> i128 {
+    values.into_iter().fold(1, |l, v| {
+        (l / gcd(l, v))
+            .checked_mul(v)
+            .expect("Common denominator should fit in an i128")
+    })
+}
+
+/// The smallest and largest `coefficient * x` over a range of `x`.
+fn extremes(coefficient: i128, range: &RangeInclusive<i64>) -> (i128, i128) {
+    let (a, b) = (
+        coefficient * i128::from(*range.start()),
+        coefficient * i128::from(*range.end()),
+    );
+    (a.min(b), a.max(b))
+}
+
+impl IntegerProgram {
+    /// A program with one variable per range, each limited to its range.
+    #[must_use]
+    pub fn new(bounds: Vec<RangeInclusive<i64>>) -> Self {
+        IntegerProgram {
+            bounds,
+            equations: Vec::new(),
+            constants: Vec::new(),
+        }
+    }
+
+    /// Require `coefficients . x = constant`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not a coefficient for every variable.
+    pub fn constrain(&mut self, coefficients: &[i64], constant: i64) {
+        assert_eq!(
+            self.bounds.len(),
+            coefficients.len(),
+            "Constraint should have a coefficient per variable"
+        );
+        self.equations.push(coefficients.to_vec());
+        self.constants.push(constant);
+    }
+
+    /// The assignment with the lowest `costs . x` meeting every constraint and bound, or `None`
+    /// if there is none. Ties go to whichever is found first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not a cost for every variable, or if reducing the equations exactly
+    /// needs numbers too large for an `i128`.
+    #[must_use]
+    pub fn minimise(&self, costs: &[i64]) -> Option<Solution> {
+        assert_eq!(
+            self.bounds.len(),
+            costs.len(),
+            "Should be a cost per variable"
+        );
+        if self.bounds.iter().any(RangeInclusive::is_empty) {
+            return None;
+        }
+        let mut search = self.search(costs)?;
+        let base = vec![0; search.rows.len()];
+        search.branch(0, &base, 0);
+        let (scaled, values) = search.best?;
+        let cost = i64::try_from(scaled / search.cost_scale).expect("Cost should fit in an i64");
+        Some(Solution { cost, values })
+    }
+
+    /// Reduce the equations and set up a search over the free variables, or `None` if the
+    /// equations have no solution at all.
+    fn search<'a>(&'a self, costs: &[i64]) -> Option<Search<'a>> {
+        let variables = self.bounds.len();
+        // Built cell by cell so a program without constraints still has a column per variable.
+        let mut augmented = Matrix::new(self.equations.len(), variables + 1);
+        for (r, (row, constant)) in self.equations.iter().zip(&self.constants).enumerate() {
+            for (c, coefficient) in row.iter().chain([constant]).enumerate() {
+                augmented[(r, c)] = (*coefficient).into();
+            }
+        }
+        let rref = augmented.rref();
+        if rref.pivots().last() == Some(&variables) {
+            return None;
+        }
+        let matrix = rref.matrix();
+        let free: Vec<_> = rref
+            .free_columns()
+            .into_iter()
+            .filter(|c| *c < variables)
+            .collect();
+
+        let rows: Vec<Row> = rref
+            .pivots()
+            .iter()
+            .enumerate()
+            .map(|(r, &pivot)| {
+                let cells: Vec<_> = free
+                    .iter()
+                    .map(|f| matrix[(r, *f)])
+                    .chain([matrix[(r, variables)]])
+                    .collect();
+                let scale = lcm(cells.iter().map(|cell| cell.denominator()));
+                let scaled = |cell: Rational| (cell * scale).numerator();
+                Row {
+                    pivot,
+                    scale,
+                    constant: scaled(matrix[(r, variables)]),
+                    coefficients: cells[..free.len()].iter().map(|c| scaled(*c)).collect(),
+                }
+            })
+            .collect();
+
+        // Substitute the pivots into the cost so it only depends on the free variables.
+        let mut reduced: Vec<Rational> = free.iter().map(|f| costs[*f].into()).collect();
+        let mut constant = Rational::ZERO;
+        for (r, &pivot) in rref.pivots().iter().enumerate() {
+            constant += matrix[(r, variables)] * costs[pivot];
+            for (i, f) in free.iter().enumerate() {
+                reduced[i] -= matrix[(r, *f)] * costs[pivot];
+            }
+        }
+        let cost_scale = lcm(reduced.iter().chain([&constant]).map(|c| c.denominator()));
+        let cost_coefficients: Vec<_> = reduced
+            .iter()
+            .map(|c| (*c * cost_scale).numerator())
+            .collect();
+
+        let suffix = |coefficient: &dyn Fn(usize) -> i128| {
+            let mut sums = vec![(0, 0); free.len() + 1];
+            for i in (0..free.len()).rev() {
+                let (low, high) = extremes(coefficient(i), &self.bounds[free[i]]);
+                sums[i] = (sums[i + 1].0 + low, sums[i + 1].1 + high);
+            }
+            sums
+        };
+        let row_ranges = rows
+            .iter()
+            .map(|row| suffix(&|i| row.coefficients[i]))
+            .collect();
+        // The constant part of the cost is folded into every minimum, so it is counted once
+        // whatever the depth.
+        let cost_constant = (constant * cost_scale).numerator();
+        let cost_minimums = suffix(&|i| cost_coefficients[i])
+            .into_iter()
+            .map(|(low, _)| cost_constant + low)
+            .collect();
+
+        let mut values = vec![0; variables];
+        for &f in &free {
+            values[f] = *self.bounds[f].start();
+        }
+        Some(Search {
+            bounds: &self.bounds,
+            free,
+            rows,
+            cost_scale,
+            cost_coefficients,
+            row_ranges,
+            cost_minimums,
+            values,
+            best: None,
+        })
+    }
+}
+
+impl Search<'_> {
+    /// Try every value of the free variable at `depth`, given the sums of the free variables
+    /// before it in each row and in the cost.
+    fn branch(&mut self, depth: usize, sums: &[i128], cost: i128) {
+        // Prune if any pivot is out of its range whatever the remaining free variables are.
+        for (r, row) in self.rows.iter().enumerate() {
+            let (low, high) = self.row_ranges[r][depth];
+            let bounds = &self.bounds[row.pivot];
+            let (smallest, largest) = (row.constant - sums[r] - high, row.constant - sums[r] - low);
+            if largest < row.scale * i128::from(*bounds.start())
+                || smallest > row.scale * i128::from(*bounds.end())
+            {
+                return;
+            }
+        }
+        if self
+            .best
+            .as_ref()
+            .is_some_and(|(best, _)| cost + self.cost_minimums[depth] >= *best)
+        {
+            return;
+        }
+        let Some(&variable) = self.free.get(depth) else {
+            self.record(sums, cost + self.cost_minimums[depth]);
+            return;
+        };
+
+        let range = self.bounds[variable].clone();
+        let ascending = self.cost_coefficients[depth] >= 0;
+        let mut value = if ascending {
+            *range.start()
+        } else {
+            *range.end()
+        };
+        let mut next = sums.to_vec();
+        loop {
+            self.values[variable] = value;
+            let x = i128::from(value);
+            for (r, row) in self.rows.iter().enumerate() {
+                next[r] = sums[r] + row.coefficients[depth] * x;
+            }
+            self.branch(depth + 1, &next, cost + self.cost_coefficients[depth] * x);
+            if value
+                == if ascending {
+                    *range.end()
+                } else {
+                    *range.start()
+                }
+            {
+                break;
+            }
+            value = if ascending { value + 1 } else { value - 1 };
+        }
+    }
+
+    /// Keep the current free variables if every pivot is a whole number within its range.
+    fn record(&mut self, sums: &[i128], cost: i128) {
+        for (r, row) in self.rows.iter().enumerate() {
+            let scaled = row.constant - sums[r];
+            if scaled % row.scale != 0 {
+                return;
+            }
+            let Ok(value) = i64::try_from(scaled / row.scale) else {
+                return;
+            };
+            if !self.bounds[row.pivot].contains(&value) {
+                return;
+            }
+            self.values[row.pivot] = value;
+        }
+        self.best = Some((cost, self.values.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Walk every assignment for the cheapest that meets the constraints.
+    fn brute_force(program: &IntegerProgram, costs: &[i64]) -> Option<i64> {
+        let mut best = None;
+        let mut values: Vec<i64> = program.bounds.iter().map(|b| *b.start()).collect();
+        loop {
+            let meets = program
+                .equations
+                .iter()
+                .zip(&program.constants)
+                .all(|(row, c)| row.iter().zip(&values).map(|(a, x)| a * x).sum::<i64>() == *c);
+            if meets {
+                let cost = costs.iter().zip(&values).map(|(c, x)| c * x).sum();
+                best = Some(best.map_or(cost, |b: i64| b.min(cost)));
+            }
+            let Some(i) = (0..values.len()).find(|i| values[*i] < *program.bounds[*i].end()) else {
+                return best;
+            };
+            values[i] += 1;
+            for (j, value) in values.iter_mut().enumerate().take(i) {
+                *value = *program.bounds[j].start();
+            }
+        }
+    }
+
+    /// A small program and the costs to minimise it under, often without a solution.
+    fn program() -> impl Strategy<Value = (IntegerProgram, Vec<i64>)> {
+        (1..=4usize).prop_flat_map(|variables| {
+            let bounds = prop::collection::vec(
+                (-2..=2i64, 0..=4i64).prop_map(|(start, len)| start..=start + len),
+                variables,
+            );
+            let equations = prop::collection::vec(
+                (prop::collection::vec(-3..=3i64, variables), -4..=4i64),
+                0..3,
+            );
+            let costs = prop::collection::vec(-3..=3i64, variables);
+            (bounds, equations, costs).prop_map(|(bounds, equations, costs)| {
+                let mut program = IntegerProgram::new(bounds);
+                for (coefficients, constant) in equations {
+                    program.constrain(&coefficients, constant);
+                }
+                (program, costs)
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force((program, costs) in program()) {
+            let solution = program.minimise(&costs);
+            prop_assert_eq!(
+                brute_force(&program, &costs),
+                solution.as_ref().map(|s| s.cost)
+            );
+            if let Some(Solution { cost, values }) = solution {
+                prop_assert_eq!(cost, costs.iter().zip(&values).map(|(c, x)| c * x).sum::<i64>());
+                for (row, constant) in program.equations.iter().zip(&program.constants) {
+                    prop_assert_eq!(
+                        *constant,
+                        row.iter().zip(&values).map(|(a, x)| a * x).sum::<i64>()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_impossible_systems() {
+        let mut program = IntegerProgram::new(vec![0..=10, 0..=10]);
+        program.constrain(&[2, 2], 5);
+        assert_eq!(None, program.minimise(&[1, 1]));
+        let mut contradiction = IntegerProgram::new(vec![0..=10]);
+        contradiction.constrain(&[1], 2);
+        contradiction.constrain(&[1], 3);
+        assert_eq!(None, contradiction.minimise(&[1]));
+    }
+}
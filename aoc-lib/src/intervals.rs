@@ -0,0 +1,316 @@
+use std::ops::{Add, Bound, RangeBounds, RangeInclusive, Sub};
+
+/// An integer type that intervals can be made of.
+pub trait Integer: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const MIN: Self;
+    const MAX: Self;
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// A set of integers stored as sorted, disjoint ranges, merging any that overlap or touch.
+///
+/// Any range works as an argument, so `3..6`, `3..=5` and `..=5` are all fine, and ranges come
+/// back out inclusive.
+///
+/// ```
+/// use aoc_lib::intervals::IntervalSet;
+///
+/// let mut set: IntervalSet<u32> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+/// assert_eq!(vec![3..=5, 10..=20], set.iter().collect::<Vec<_>>());
+/// assert!(set.contains(17) && !set.contains(8));
+/// set.remove(12..15);
+/// assert_eq!(11, set.total_len());
+/// assert_eq!(vec![0..=2, 6..=9, 12..=14], set.complement(..15).iter().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Inclusive `(start, end)` pairs in order, with a gap of at least one between each.
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+/// The inclusive ends of a range, or `None` if it is empty.
+fn inclusive<T: Integer>(range: &impl RangeBounds<T>) -> Option<(T, T)> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) if start == T::MAX => return None,
+        Bound::Excluded(&start) => start + T::ONE,
+        Bound::Unbounded => T::MIN,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&end) if end == T::MIN => return None,
+        Bound::Excluded(&end) => end - T::ONE,
+        Bound::Unbounded => T::MAX,
+    };
+    (start <= end).then_some((start, end))
+}
+
+impl<T: Integer> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Add every value in a range.
+    pub fn insert(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = inclusive(&range) else {
+            return;
+        };
+        // Ranges that end more than one before the start, or begin more than one after the end,
+        // are left alone. Everything between is merged.
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e < start && e + T::ONE < start);
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| s <= end || s - T::ONE == end);
+        let merged = match self.ranges.get(first..last) {
+            Some([(s, _), .., (_, e)] | [(s, e)]) => (start.min(*s), end.max(*e)),
+            _ => (start, end),
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Take every value in a range out of the set.
+    pub fn remove(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = inclusive(&range) else {
+            return;
+        };
+        let first = self.ranges.partition_point(|&(_, e)| e < start);
+        let last = self.ranges.partition_point(|&(s, _)| s <= end);
+        if first == last {
+            return;
+        }
+        let (s, e) = (self.ranges[first].0, self.ranges[last - 1].1);
+        let left = (s < start).then(|| (s, start - T::ONE));
+        let right = (e > end).then(|| (end + T::ONE, e));
+        self.ranges
+            .splice(first..last, left.into_iter().chain(right));
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, e)| e < value);
+        self.ranges.get(i).is_some_and(|&(s, _)| s <= value)
+    }
+
+    /// How many values are in the set, which must fit in `T`.
+    #[must_use]
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |total, &(s, e)| total + (e - s) + T::ONE)
+    }
+
+    /// The ranges of the set in order, with gaps between each.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// Every value in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for &(s, e) in &other.ranges {
+            union.insert(s..=e);
+        }
+        union
+    }
+
+    /// Every value in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(&&(sa, ea)), Some(&&(sb, eb))) = (a.peek(), b.peek()) {
+            let (start, end) = (sa.max(sb), ea.min(eb));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if ea < eb {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// Every value in a range that is not in the set.
+    #[must_use]
+    pub fn complement(&self, within: impl RangeBounds<T>) -> Self {
+        let Some((start, end)) = inclusive(&within) else {
+            return Self::new();
+        };
+        let mut ranges = Vec::new();
+        // The first value not yet known to be covered, or `None` once the end is covered.
+        let mut next = Some(start);
+        for &(s, e) in &self.ranges {
+            let Some(from) = next else {
+                break;
+            };
+            if s > end {
+                break;
+            }
+            if e < from {
+                continue;
+            }
+            if s > from {
+                ranges.push((from, s - T::ONE));
+            }
+            next = (e < end).then(|| e + T::ONE);
+        }
+        if let Some(from) = next {
+            ranges.push((from, end));
+        }
+        IntervalSet { ranges }
+    }
+}
+
+impl<T: Integer, R: RangeBounds<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Integer, R: RangeBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        set.extend(ranges);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn handles_the_ends_of_the_type() {
+        let mut set = IntervalSet::new();
+        set.insert(250u8..);
+        set.insert(..=3);
+        set.insert(4..4);
+        assert_eq!(vec![0..=3, 250..=255], set.iter().collect::<Vec<_>>());
+        assert!(set.contains(u8::MAX) && set.contains(u8::MIN));
+        assert_eq!(vec![4..=249], set.complement(..).iter().collect::<Vec<_>>());
+        set.insert(4..250);
+        assert_eq!(vec![0..=255], set.iter().collect::<Vec<_>>());
+        set.remove(..);
+        assert!(set.is_empty());
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Insert(i8, i8),
+        InsertHalfOpen(i8, i8),
+        Remove(i8, i8),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (any::<i8>(), any::<i8>()).prop_map(|(a, b)| Op::Insert(a, b)),
+            (any::<i8>(), any::<i8>()).prop_map(|(a, b)| Op::InsertHalfOpen(a, b)),
+            (any::<i8>(), any::<i8>()).prop_map(|(a, b)| Op::Remove(a, b)),
+        ]
+    }
+
+    /// Apply operations to an interval set and a set of every value.
+    fn build(ops: &[Op]) -> (IntervalSet<i8>, BTreeSet<i8>) {
+        let mut set = IntervalSet::new();
+        let mut model = BTreeSet::new();
+        for op in ops {
+            match *op {
+                Op::Insert(a, b) => {
+                    set.insert(a..=b);
+                    model.extend(a..=b);
+                }
+                Op::InsertHalfOpen(a, b) => {
+                    set.insert(a..b);
+                    model.extend(a..b);
+                }
+                Op::Remove(a, b) => {
+                    set.remove(a..=b);
+                    model.retain(|v| !(a..=b).contains(v));
+                }
+            }
+        }
+        (set, model)
+    }
+
+    /// Check an interval set holds the same values as the model, with its ranges in order and
+    /// never touching.
+    fn check(set: &IntervalSet<i8>, model: &BTreeSet<i8>) -> Result<(), TestCaseError> {
+        for value in i8::MIN..=i8::MAX {
+            prop_assert_eq!(model.contains(&value), set.contains(value), "{}", value);
+        }
+        for pair in set.ranges.windows(2) {
+            prop_assert!(i16::from(pair[0].1) + 1 < i16::from(pair[1].0));
+        }
+        if let Ok(len) = i8::try_from(model.len()) {
+            prop_assert_eq!(len, set.total_len());
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn matches_a_set_of_values(ops in prop::collection::vec(op(), 0..20)) {
+            let (set, model) = build(&ops);
+            check(&set, &model)?;
+        }
+
+        #[test]
+        fn combines_like_sets_of_values(
+            a in prop::collection::vec(op(), 0..10),
+            b in prop::collection::vec(op(), 0..10),
+            start: i8,
+            end: i8,
+        ) {
+            let ((set_a, model_a), (set_b, model_b)) = (build(&a), build(&b));
+            check(&set_a.union(&set_b), &model_a.union(&model_b).copied().collect())?;
+            check(
+                &set_a.intersection(&set_b),
+                &model_a.intersection(&model_b).copied().collect(),
+            )?;
+            check(
+                &set_a.complement(start..=end),
+                &(start..=end).filter(|v| !model_a.contains(v)).collect(),
+            )?;
+        }
+    }
+}
@@ -1,2 +1,14 @@
+pub mod bitset;
+pub mod dial;
+pub mod direction;
+pub mod gf2;
+pub mod grid;
+pub mod ilp;
+pub mod intervals;
+pub mod linalg;
+pub mod parse;
+pub mod point;
 pub mod runner;
+pub mod sparse;
 pub mod timer;
+pub mod union_find;
@@ -0,0 +1,596 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+/// An exact fraction, always kept in lowest terms with a positive denominator.
+///
+/// Common factors are cancelled before multiplying, so intermediate values stay as small as the
+/// result allows. Arithmetic whose result still does not fit in an `i128` panics rather than
+/// wrapping.
+///
+/// ```
+/// use aoc_lib::linalg::Rational;
+///
+/// let third = Rational::new(1, 3);
+/// assert_eq!(Rational::ONE, third * 3);
+/// assert_eq!(Rational::new(-1, 6), third / -2);
+/// assert_eq!("5/6", (third + Rational::new(1, 2)).to_string());
+/// assert_eq!(Some(2), (third * 6).to_integer());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    // Unsigned, as `i128::MIN` has no positive `i128` to take the absolute value as.
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    checked(i128::try_from(a).ok())
+}
+
+fn checked(value: Option<i128>) -> i128 {
+    value.expect("Rational arithmetic should fit in an i128")
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational::integer(0);
+    pub const ONE: Rational = Rational::integer(1);
+
+    /// A fraction reduced to lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the denominator is zero, or the reduced fraction does not fit in an `i128`, such
+    /// as `i128::MIN / -1`.
+    #[must_use]
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "Denominator should not be zero");
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Rational {
+            numerator: checked(numerator.checked_div(divisor)),
+            denominator: checked(denominator.checked_div(divisor)),
+        }
+    }
+
+    #[must_use]
+    pub const fn integer(value: i128) -> Self {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    #[must_use]
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    #[must_use]
+    pub fn is_zero(self) -> bool {
+        self.numerator == 0
+    }
+
+    #[must_use]
+    pub fn is_integer(self) -> bool {
+        self.denominator == 1
+    }
+
+    /// The value if it is a whole number.
+    #[must_use]
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    /// One over the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is zero.
+    #[must_use]
+    pub fn recip(self) -> Self {
+        Rational::new(self.denominator, self.numerator)
+    }
+
+    /// The largest integer no greater than the value.
+    #[must_use]
+    pub fn floor(self) -> i128 {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    /// The smallest integer no less than the value.
+    #[must_use]
+    pub fn ceil(self) -> i128 {
+        -(-self).floor()
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Rational {
+            fn from(value: $t) -> Self {
+                Rational::integer(i128::from(value))
+            }
+        })*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+/// # Panics
+///
+/// Panics if cross multiplying overflows an `i128`.
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive, so cross multiplying keeps the order.
+        let common = gcd(self.denominator, other.denominator);
+        let left = self.numerator.checked_mul(other.denominator / common);
+        let right = other.numerator.checked_mul(self.denominator / common);
+        checked(left).cmp(&checked(right))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Rational {
+            numerator: checked(self.numerator.checked_neg()),
+            denominator: self.denominator,
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if the result overflows an `i128`.
+impl<T: Into<Rational>> Add<T> for Rational {
+    type Output = Self;
+
+    fn add(self, other: T) -> Self {
+        let other = other.into();
+        // Scale both to the lowest common denominator rather than the product of the two.
+        let common = gcd(self.denominator, other.denominator);
+        let (left, right) = (self.denominator / common, other.denominator / common);
+        let numerator = checked(self.numerator.checked_mul(right))
+            .checked_add(checked(other.numerator.checked_mul(left)));
+        Rational::new(
+            checked(numerator),
+            checked(self.denominator.checked_mul(right)),
+        )
+    }
+}
+
+impl<T: Into<Rational>> Sub<T> for Rational {
+    type Output = Self;
+
+    fn sub(self, other: T) -> Self {
+        self + -other.into()
+    }
+}
+
+/// # Panics
+///
+/// Panics if the result overflows an `i128`.
+impl<T: Into<Rational>> Mul<T> for Rational {
+    type Output = Self;
+
+    fn mul(self, other: T) -> Self {
+        let other = other.into();
+        // Both are in lowest terms, so cancelling across is enough to keep the product reduced.
+        let (a, b) = (
+            gcd(self.numerator, other.denominator),
+            gcd(other.numerator, self.denominator),
+        );
+        Rational::new(
+            checked((self.numerator / a).checked_mul(other.numerator / b)),
+            checked((self.denominator / b).checked_mul(other.denominator / a)),
+        )
+    }
+}
+
+/// # Panics
+///
+/// Panics when dividing by zero, or if the result overflows an `i128`.
+impl<T: Into<Rational>> Div<T> for Rational {
+    type Output = Self;
+
+    fn div(self, other: T) -> Self {
+        let other = other.into();
+        Mul::mul(self, other.recip())
+    }
+}
+
+impl<T: Into<Rational>> AddAssign<T> for Rational {
+    fn add_assign(&mut self, other: T) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Into<Rational>> SubAssign<T> for Rational {
+    fn sub_assign(&mut self, other: T) {
+        *self = *self - other;
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// A dense matrix of exact fractions, stored row by row.
+///
+/// ```
+/// use aoc_lib::linalg::{Matrix, Rational};
+///
+/// // x + y = 3, y + z = 5: one free variable, so a line of solutions.
+/// let matrix = Matrix::from_rows([[1, 1, 0], [0, 1, 1]]);
+/// let rref = matrix.rref();
+/// assert_eq!(&[0, 1], rref.pivots());
+/// assert_eq!(vec![2], rref.free_columns());
+/// assert_eq!(vec![vec![Rational::ONE, -Rational::ONE, Rational::ONE]], matrix.null_space());
+/// let solution = matrix.solve(&[3.into(), 5.into()]).unwrap();
+/// assert_eq!(vec![Rational::from(-2), 5.into(), 0.into()], solution);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix {
+    cells: Vec<Rational>,
+    rows: usize,
+    columns: usize,
+}
+
+impl Matrix {
+    /// A matrix of zeros.
+    #[must_use]
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Matrix {
+            cells: vec![Rational::ZERO; rows * columns],
+            rows,
+            columns,
+        }
+    }
+
+    /// A matrix from its rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows are not all the same length.
+    #[must_use]
+    pub fn from_rows<R, T>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+        T: Into<Rational>,
+    {
+        let mut cells = Vec::new();
+        let mut columns = 0;
+        let mut height = 0;
+        for row in rows {
+            let before = cells.len();
+            cells.extend(row.into_iter().map(Into::into));
+            let width = cells.len() - before;
+            if height == 0 {
+                columns = width;
+            }
+            assert_eq!(columns, width, "Row {height} should be {columns} wide");
+            height += 1;
+        }
+        Matrix {
+            cells,
+            rows: height,
+            columns,
+        }
+    }
+
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// The cells of a row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row is outside the matrix.
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[Rational] {
+        assert!(row < self.rows, "Row {row} should be in the matrix");
+        &self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    /// A copy of the matrix with an extra column on the right, such as the constants of a system
+    /// of equations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is not as tall as the matrix.
+    #[must_use]
+    pub fn augment(&self, column: &[Rational]) -> Self {
+        assert_eq!(
+            self.rows,
+            column.len(),
+            "Column should have a value per row"
+        );
+        Matrix::from_rows((0..self.rows).map(|r| self.row(r).iter().copied().chain([column[r]])))
+    }
+
+    /// Reduced row echelon form, by Gauss-Jordan elimination.
+    #[must_use]
+    pub fn rref(&self) -> Rref {
+        let mut matrix = self.clone();
+        let mut pivots = Vec::new();
+        for column in 0..self.columns {
+            let row = pivots.len();
+            let Some(found) = (row..self.rows).find(|r| !matrix[(*r, column)].is_zero()) else {
+                continue;
+            };
+            matrix.swap_rows(row, found);
+            let scale = matrix[(row, column)];
+            for c in column..self.columns {
+                matrix[(row, c)] = matrix[(row, c)] / scale;
+            }
+            for r in (0..self.rows).filter(|r| *r != row) {
+                let factor = matrix[(r, column)];
+                if factor.is_zero() {
+                    continue;
+                }
+                for c in column..self.columns {
+                    let delta = matrix[(row, c)] * factor;
+                    matrix[(r, c)] -= delta;
+                }
+            }
+            pivots.push(column);
+            if pivots.len() == self.rows {
+                break;
+            }
+        }
+        Rref { matrix, pivots }
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for c in 0..self.columns {
+                self.cells.swap(a * self.columns + c, b * self.columns + c);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        self.rref().rank()
+    }
+
+    /// A basis of the vectors `v` with `self * v = 0`.
+    #[must_use]
+    pub fn null_space(&self) -> Vec<Vec<Rational>> {
+        self.rref().null_space()
+    }
+
+    /// A solution to `self * x = rhs`, with every free variable zero, or `None` if there is none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is not as tall as the matrix.
+    #[must_use]
+    pub fn solve(&self, rhs: &[Rational]) -> Option<Vec<Rational>> {
+        let rref = self.augment(rhs).rref();
+        if rref.pivots.last() == Some(&self.columns) {
+            return None;
+        }
+        let mut solution = vec![Rational::ZERO; self.columns];
+        for (row, column) in rref.pivots.iter().enumerate() {
+            solution[*column] = rref.matrix[(row, self.columns)];
+        }
+        Some(solution)
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = Rational;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Rational {
+        assert!(
+            row < self.rows && column < self.columns,
+            "({row}, {column}) should be in the matrix"
+        );
+        &self.cells[row * self.columns + column]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Rational {
+        assert!(
+            row < self.rows && column < self.columns,
+            "({row}, {column}) should be in the matrix"
+        );
+        &mut self.cells[row * self.columns + column]
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for r in 0..self.rows {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            let row: Vec<_> = self.row(r).iter().map(ToString::to_string).collect();
+            write!(f, "{}", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A matrix in reduced row echelon form, with the column of each row's leading one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rref {
+    matrix: Matrix,
+    pivots: Vec<usize>,
+}
+
+impl Rref {
+    /// The reduced matrix. Rows past the rank are all zero.
+    #[must_use]
+    pub fn matrix(&self) -> &Matrix {
+        &self.matrix
+    }
+
+    /// The pivot column of each non-zero row, in order.
+    #[must_use]
+    pub fn pivots(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// The columns without a pivot, whose variables can take any value.
+    #[must_use]
+    pub fn free_columns(&self) -> Vec<usize> {
+        (0..self.matrix.columns)
+            .filter(|c| !self.pivots.contains(c))
+            .collect()
+    }
+
+    /// A basis of the null space, with one vector per free column that sets it to one.
+    #[must_use]
+    pub fn null_space(&self) -> Vec<Vec<Rational>> {
+        self.free_columns()
+            .into_iter()
+            .map(|free| {
+                let mut vector = vec![Rational::ZERO; self.matrix.columns];
+                vector[free] = Rational::ONE;
+                for (row, pivot) in self.pivots.iter().enumerate() {
+                    vector[*pivot] = -self.matrix[(row, free)];
+                }
+                vector
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rationals_stay_reduced() {
+        assert_eq!(Rational::new(1, 2), Rational::new(-3, -6));
+        let half = Rational::new(3, -6);
+        assert_eq!((-1, 2), (half.numerator(), half.denominator()));
+        assert_eq!(
+            (-2, -1),
+            (Rational::new(-3, 2).floor(), Rational::new(-3, 2).ceil())
+        );
+        assert_eq!(
+            (1, 2),
+            (Rational::new(3, 2).floor(), Rational::new(3, 2).ceil())
+        );
+        assert!(Rational::new(-1, 2) < Rational::new(1, 3));
+        assert!(Rational::new(2, 3) > Rational::new(3, 5));
+        assert_eq!(None, Rational::new(3, 2).to_integer());
+        assert_eq!(Rational::ZERO, Rational::new(1, 6) - Rational::new(2, 12));
+    }
+
+    #[test]
+    fn cancels_before_multiplying() {
+        let big = 1 << 100;
+        let tiny = Rational::new(1, big);
+        assert_eq!(Rational::new(1, 3), tiny * Rational::new(big, 3));
+        assert_eq!(Rational::new(1, big / 2), tiny + tiny);
+        assert_eq!(Rational::ONE, tiny / tiny);
+        assert!(tiny < Rational::new(2, big));
+    }
+
+    #[test]
+    fn handles_the_smallest_i128() {
+        let min = Rational::new(i128::MIN, 2);
+        assert_eq!((i128::MIN / 2, 1), (min.numerator(), min.denominator()));
+        assert_eq!(Rational::new(1, 2), Rational::new(i128::MIN / 2, i128::MIN));
+    }
+
+    #[test]
+    #[should_panic(expected = "Rational arithmetic should fit in an i128")]
+    fn panics_on_negating_the_smallest_i128() {
+        let _ = Rational::new(i128::MIN, -1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Rational arithmetic should fit in an i128")]
+    fn panics_on_overflow() {
+        let _ = Rational::new(1, 1 << 100) * Rational::new(1, 1 << 100);
+    }
+
+    fn multiply(matrix: &Matrix, vector: &[Rational]) -> Vec<Rational> {
+        (0..matrix.rows())
+            .map(|r| {
+                matrix
+                    .row(r)
+                    .iter()
+                    .zip(vector)
+                    .fold(Rational::ZERO, |total, (a, b)| total + *a * *b)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reduces_without_rounding() {
+        // The buttons and joltages of the first 2025 day 10 sample.
+        let matrix = Matrix::from_rows([
+            [0, 0, 0, 0, 1, 1],
+            [0, 1, 0, 0, 0, 1],
+            [0, 0, 1, 1, 1, 0],
+            [1, 1, 0, 1, 0, 0],
+        ]);
+        let rref = matrix.rref();
+        assert_eq!(4, rref.rank());
+        assert_eq!(vec![3, 5], rref.free_columns());
+        let rhs: Vec<Rational> = [3, 5, 4, 7].map(Rational::from).into();
+        let solution = matrix.solve(&rhs).expect("Should be solvable");
+        assert_eq!(rhs, multiply(&matrix, &solution));
+        for vector in matrix.null_space() {
+            assert_eq!(vec![Rational::ZERO; 4], multiply(&matrix, &vector));
+        }
+    }
+
+    #[test]
+    fn detects_inconsistent_systems() {
+        let matrix = Matrix::from_rows([[1, 2], [2, 4], [3, 6]]);
+        assert_eq!(1, matrix.rank());
+        assert_eq!(None, matrix.solve(&[1.into(), 3.into(), 3.into()]));
+        assert_eq!(
+            Some(vec![Rational::from(1), Rational::ZERO]),
+            matrix.solve(&[1.into(), 2.into(), 3.into()])
+        );
+        assert_eq!("1 2\n0 0\n0 0", matrix.rref().matrix().to_string());
+        let thirds = Matrix::from_rows([[3, 1], [0, 3]]);
+        assert_eq!(
+            Some(vec![Rational::new(1, 9), Rational::new(1, 3)]),
+            thirds.solve(&[Rational::new(2, 3), Rational::ONE])
+        );
+    }
+}
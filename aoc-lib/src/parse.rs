@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// Split the input into lines.
+///
+/// ```
+/// use aoc_lib::parse;
+///
+/// let lines: Vec<_> = parse::lines("L68\nL30").collect();
+/// assert_eq!(vec!["L68", "L30"], lines);
+/// ```
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.split('\n')
+}
+
+/// Split the input into blocks separated by blank lines.
+///
+/// ```
+/// use aoc_lib::parse;
+///
+/// let blocks: Vec<_> = parse::blocks("3-5\n10-14\n\n1\n5").collect();
+/// assert_eq!(vec!["3-5\n10-14", "1\n5"], blocks);
+/// ```
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    input.split("\n\n")
+}
+
+/// Parse a list of values separated by a delimiter, such as a comma separated line.
+///
+/// # Panics
+///
+/// Panics if any value fails to parse, as puzzle inputs are assumed to be well formed.
+///
+/// ```
+/// use aoc_lib::parse;
+///
+/// let values: Vec<u32> = parse::list("11,22,95", ',');
+/// assert_eq!(vec![11, 22, 95], values);
+/// ```
+#[must_use]
+pub fn list<T>(input: &str, delimiter: char) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    input
+        .split(delimiter)
+        .map(|value| value.trim().parse().expect("Should be a valid value"))
+        .collect()
+}
+
+/// Parse a graph given as one node per line, followed by a colon and its space separated neighbours.
+///
+/// # Panics
+///
+/// Panics if a non-empty line has no colon.
+///
+/// ```
+/// use aoc_lib::parse;
+///
+/// let graph = parse::graph("you: bbb ccc\nbbb: out");
+/// assert_eq!(vec!["bbb", "ccc"], graph["you"]);
+/// assert_eq!(vec!["out"], graph["bbb"]);
+/// ```
+#[must_use]
+pub fn graph(input: &str) -> HashMap<&str, Vec<&str>> {
+    lines(input)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (node, neighbours) = line.split_once(':').expect("Should be a ':'");
+            (node.trim(), neighbours.split_whitespace().collect())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_without_neighbours() {
+        let graph = graph("a: b\nb:");
+        assert_eq!(vec!["b"], graph["a"]);
+        assert!(graph["b"].is_empty());
+        assert!(super::graph("").is_empty());
+    }
+
+    #[test]
+    fn list_trims_values() {
+        assert_eq!(vec![1, 2, 3], list::<u8>("1, 2 ,3", ','));
+    }
+}
@@ -0,0 +1,317 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A point or vector on a plane.
+///
+/// Points order by `x` then `y`, and hash, so they can be used as keys of any map or set.
+///
+/// ```
+/// use aoc_lib::point::Point2;
+///
+/// let a: Point2<i64> = "7,1".parse().unwrap();
+/// let b = Point2::new(11, 7);
+/// assert_eq!(Point2::new(4, 6), b - a);
+/// assert_eq!(10, a.manhattan(b));
+/// assert_eq!(52, a.distance_squared(b));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point or vector in space.
+///
+/// ```
+/// use aoc_lib::point::Point3;
+///
+/// let a: Point3<usize> = "162,817,812".parse().unwrap();
+/// let b = Point3::new(425, 690, 689);
+/// assert_eq!(100_427, a.distance_squared(b));
+/// assert_eq!(Point3::new(324, 1634, 1624), a * 2);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+/// Difference between two coordinates, which works for unsigned types as well as signed ones.
+fn distance<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Distance moving only along the axes.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// Square of the straight line distance, which orders points the same way without a square root.
+    #[must_use]
+    pub fn distance_squared(self, other: Self) -> T
+    where
+        T: Mul<Output = T>,
+    {
+        let (dx, dy) = (distance(self.x, other.x), distance(self.y, other.y));
+        dx * dx + dy * dy
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Distance moving only along the axes.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+
+    /// Square of the straight line distance, which orders points the same way without a square root.
+    #[must_use]
+    pub fn distance_squared(self, other: Self) -> T
+    where
+        T: Mul<Output = T>,
+    {
+        let (dx, dy, dz) = (
+            distance(self.x, other.x),
+            distance(self.y, other.y),
+            distance(self.z, other.z),
+        );
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// Scale a vector.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Point2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+/// Scale a vector.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// A point was not the expected number of comma separated coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePointError {
+    input: String,
+}
+
+impl Display for ParsePointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid point: {}", self.input)
+    }
+}
+
+impl Error for ParsePointError {}
+
+/// Parse exactly `N` comma separated coordinates.
+fn coordinates<T: FromStr, const N: usize>(input: &str) -> Result<[T; N], ParsePointError> {
+    let invalid = || ParsePointError {
+        input: input.to_owned(),
+    };
+    let values: Vec<T> = input
+        .split(',')
+        .map(|value| value.trim().parse().map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    values.try_into().map_err(|_| invalid())
+}
+
+/// Parse `x,y`.
+impl<T: FromStr> FromStr for Point2<T> {
+    type Err = ParsePointError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let [x, y] = coordinates(input)?;
+        Ok(Point2::new(x, y))
+    }
+}
+
+/// Parse `x,y,z`.
+impl<T: FromStr> FromStr for Point3<T> {
+    type Err = ParsePointError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = coordinates(input)?;
+        Ok(Point3::new(x, y, z))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn parses_points() {
+        assert_eq!(Ok(Point2::new(-3, 4)), " -3, 4".parse::<Point2<i32>>());
+        assert_eq!(Ok(Point3::new(1, 2, 3)), "1,2,3".parse::<Point3<u8>>());
+        "1,2,3"
+            .parse::<Point2<i32>>()
+            .expect_err("Too many coordinates");
+        "1,2"
+            .parse::<Point3<i32>>()
+            .expect_err("Too few coordinates");
+        "1,x".parse::<Point2<i32>>().expect_err("Not a number");
+        "-1,2"
+            .parse::<Point2<u32>>()
+            .expect_err("Negative unsigned");
+        assert_eq!("-3,4", Point2::new(-3, 4).to_string());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point2::new(1, 2);
+        p += Point2::new(3, -4);
+        assert_eq!(Point2::new(4, -2), p);
+        p -= Point2::new(4, 0);
+        assert_eq!(Point2::new(0, -2), p);
+        assert_eq!(Point2::new(0, -6), p * 3);
+        assert_eq!(
+            Point3::new(2, 0, -2),
+            Point3::new(1, 1, 1) - Point3::new(-1, 1, 3)
+        );
+    }
+
+    #[test]
+    fn unsigned_distances() {
+        let a = Point3::<usize>::new(1, 10, 5);
+        let b = Point3::new(4, 6, 5);
+        assert_eq!(7, a.manhattan(b));
+        assert_eq!(7, b.manhattan(a));
+        assert_eq!(25, a.distance_squared(b));
+    }
+
+    #[test]
+    fn orders_by_x_then_y() {
+        let points: BTreeSet<_> = [(2, 1), (1, 5), (1, 2)].map(Point2::from).into();
+        assert_eq!(
+            vec![(1, 2), (1, 5), (2, 1)],
+            points
+                .into_iter()
+                .map(<(i32, i32)>::from)
+                .collect::<Vec<_>>()
+        );
+    }
+}
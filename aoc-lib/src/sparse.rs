@@ -0,0 +1,277 @@
+use crate::direction::{Direction4, Direction8};
+use crate::point::Point2;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Points on the plane have `y` growing downwards like the rows of an input.
+type Point = Point2<i64>;
+
+/// The smallest rectangle holding a set of points, with both corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point2<i64>,
+    pub max: Point2<i64>,
+}
+
+impl Bounds {
+    #[must_use]
+    pub fn width(&self) -> u64 {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    #[must_use]
+    pub fn height(&self) -> u64 {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point2<i64>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    fn on_edge(&self, Point { x, y }: Point) -> bool {
+        x == self.min.x || x == self.max.x || y == self.min.y || y == self.max.y
+    }
+
+    /// Grow bounds, if there are any yet, to include a point.
+    fn include(bounds: Option<Bounds>, point: Point) -> Bounds {
+        match bounds {
+            Some(b) => Bounds {
+                min: Point::new(b.min.x.min(point.x), b.min.y.min(point.y)),
+                max: Point::new(b.max.x.max(point.x), b.max.y.max(point.y)),
+            },
+            None => Bounds {
+                min: point,
+                max: point,
+            },
+        }
+    }
+}
+
+/// The up, right, down and left neighbours of a point.
+pub fn neighbours4(point: Point2<i64>) -> impl Iterator<Item = Point2<i64>> {
+    Direction4::ALL
+        .into_iter()
+        .map(move |direction| point + direction.delta())
+}
+
+/// All eight neighbours of a point, including diagonals.
+pub fn neighbours8(point: Point2<i64>) -> impl Iterator<Item = Point2<i64>> {
+    Direction8::ALL
+        .into_iter()
+        .map(move |direction| point + direction.delta())
+}
+
+/// A grid which only stores occupied points, so it can grow in any direction.
+///
+/// ```
+/// use aoc_lib::point::Point2;
+/// use aoc_lib::sparse::SparseGrid;
+///
+/// let mut grid = SparseGrid::parse("#.\n.#", |c| (c == '#').then_some('#'));
+/// grid.insert(Point2::new(-2, 0), '#');
+/// assert_eq!(3, grid.len());
+/// assert_eq!("#.#.\n...#", grid.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a character map with the top left at the origin, keeping the cells the mapping
+    /// function returns a value for.
+    #[must_use]
+    pub fn parse<F>(input: &str, mut cell: F) -> Self
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut grid = Self::new();
+        for (y, line) in (0..).zip(input.lines()) {
+            for (x, c) in (0..).zip(line.chars()) {
+                if let Some(value) = cell(c) {
+                    grid.insert(Point::new(x, y), value);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Set the value at a point, returning the previous value if there was one.
+    pub fn insert(&mut self, point: Point2<i64>, value: T) -> Option<T> {
+        self.bounds = Some(Bounds::include(self.bounds, point));
+        self.cells.insert(point, value)
+    }
+
+    /// Clear a point, returning its value if there was one.
+    pub fn remove(&mut self, point: Point2<i64>) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+        // Only points on the edge can shrink the bounds.
+        if self.bounds.is_some_and(|bounds| bounds.on_edge(point)) {
+            self.bounds = self
+                .cells
+                .keys()
+                .fold(None, |bounds, p| Some(Bounds::include(bounds, *p)));
+        }
+        Some(removed)
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point2<i64>) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point2<i64>) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point2<i64>) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The bounding box of the occupied points, or `None` if the grid is empty.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    /// Every occupied point and its value, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<i64>, &T)> {
+        self.cells.iter().map(|(point, value)| (*point, value))
+    }
+
+    /// The occupied neighbours of a point, including diagonals, with their values.
+    pub fn occupied_neighbours(
+        &self,
+        point: Point2<i64>,
+    ) -> impl Iterator<Item = (Point2<i64>, &T)> {
+        neighbours8(point).filter_map(|n| self.get(n).map(|value| (n, value)))
+    }
+
+    /// Draw the occupied region, one line per row, choosing what to show for every point.
+    pub fn render<F, D>(&self, mut cell: F) -> String
+    where
+        F: FnMut(Option<&T>) -> D,
+        D: Display,
+    {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+        let mut out = String::new();
+        for y in bounds.min.y..=bounds.max.y {
+            if y > bounds.min.y {
+                out.push('\n');
+            }
+            for x in bounds.min.x..=bounds.max.x {
+                out.push_str(&cell(self.get(Point::new(x, y))).to_string());
+            }
+        }
+        out
+    }
+}
+
+/// Render the occupied region with each cell's `Display`, and `.` for empty points.
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(|cell| cell.map_or_else(|| ".".to_owned(), T::to_string)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(None, grid.bounds());
+        grid.insert(Point::new(3, -2), 1);
+        grid.insert(Point::new(-4, 5), 2);
+        grid.insert(Point::new(0, 0), 3);
+        let bounds = grid.bounds().expect("Should have bounds");
+        assert_eq!(
+            (Point::new(-4, -2), Point::new(3, 5)),
+            (bounds.min, bounds.max)
+        );
+        assert_eq!((8, 8), (bounds.width(), bounds.height()));
+        assert!(bounds.contains(Point::new(0, 0)));
+        assert!(!bounds.contains(Point::new(4, 0)));
+
+        assert_eq!(Some(2), grid.remove(Point::new(-4, 5)));
+        let shrunk = grid.bounds().expect("Should have bounds");
+        assert_eq!(
+            (Point::new(0, -2), Point::new(3, 0)),
+            (shrunk.min, shrunk.max)
+        );
+        assert_eq!(None, grid.remove(Point::new(-4, 5)));
+        grid.remove(Point::new(0, 0));
+        grid.remove(Point::new(3, -2));
+        assert_eq!(None, grid.bounds());
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn renders_occupied_region() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Point::new(-1, -1), 'a');
+        grid.insert(Point::new(1, 0), 'b');
+        assert_eq!("a..\n..b", grid.to_string());
+        assert_eq!(
+            "#  \n  #",
+            grid.render(|cell| if cell.is_some() { '#' } else { ' ' })
+        );
+        assert_eq!("", SparseGrid::<char>::new().to_string());
+        let mut wide = SparseGrid::new();
+        wide.insert(Point::new(0, 0), 10);
+        wide.insert(Point::new(1, 1), 7);
+        assert_eq!("10.\n.7", wide.to_string());
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = SparseGrid::parse("#.#\n.#.\n..#", |c| (c == '#').then_some(()));
+        assert_eq!(4, grid.len());
+        assert_eq!(
+            vec![
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ],
+            neighbours4(Point::new(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(8, neighbours8(Point::new(0, 0)).count());
+        let mut occupied: Vec<_> = grid
+            .occupied_neighbours(Point::new(1, 1))
+            .map(|(p, ())| p.into())
+            .collect();
+        occupied.sort_unstable();
+        assert_eq!(vec![(0, 0), (2, 0), (2, 2)], occupied);
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Two components joined by a union: `root` now represents every member of `absorbed`, and the
+/// merged component has `size` members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Merge<T> {
+    pub root: T,
+    pub absorbed: T,
+    pub size: usize,
+}
+
+/// Union-find over the items `0` to `len - 1`, with path compression and union by size.
+///
+/// ```
+/// use aoc_lib::union_find::DisjointSet;
+///
+/// let mut set = DisjointSet::new(5);
+/// assert!(set.union(0, 1));
+/// assert!(set.union(3, 4));
+/// assert!(!set.union(1, 0));
+/// assert!(set.same(4, 3));
+/// assert_eq!(3, set.num_components());
+/// assert_eq!(vec![vec![0, 1], vec![2], vec![3, 4]], set.components().collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    num_components: usize,
+}
+
+impl DisjointSet {
+    /// A set of `len` items, each in a component of its own.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            num_components: len,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Add a new item in a component of its own, returning it.
+    pub fn push(&mut self) -> usize {
+        let item = self.len();
+        self.parents.push(item);
+        self.sizes.push(1);
+        self.num_components += 1;
+        item
+    }
+
+    /// The root of an item's component, shortening the path to it for later lookups.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item is not in the set.
+    pub fn find(&mut self, item: usize) -> usize {
+        let root = self.component_of(item);
+        let mut current = item;
+        while current != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+        root
+    }
+
+    /// The root of an item's component, without changing the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item is not in the set.
+    #[must_use]
+    pub fn component_of(&self, item: usize) -> usize {
+        let mut current = item;
+        while self.parents[current] != current {
+            current = self.parents[current];
+        }
+        current
+    }
+
+    /// Whether two items are in the same component.
+    #[must_use]
+    pub fn same(&self, a: usize, b: usize) -> bool {
+        self.component_of(a) == self.component_of(b)
+    }
+
+    /// The number of items in an item's component.
+    #[must_use]
+    pub fn size_of(&self, item: usize) -> usize {
+        self.sizes[self.component_of(item)]
+    }
+
+    #[must_use]
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    /// Join the components of two items, returning whether they were separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        self.union_with(a, b, |_| {})
+    }
+
+    /// Join the components of two items, calling `on_merge` if they were separate. Returns whether
+    /// they were.
+    pub fn union_with<F>(&mut self, a: usize, b: usize, on_merge: F) -> bool
+    where
+        F: FnOnce(Merge<usize>),
+    {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+        let (root, absorbed) = if self.sizes[root_a] >= self.sizes[root_b] {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.parents[absorbed] = root;
+        self.sizes[root] += self.sizes[absorbed];
+        self.num_components -= 1;
+        on_merge(Merge {
+            root,
+            absorbed,
+            size: self.sizes[root],
+        });
+        true
+    }
+
+    /// The size of every component, in the order of their roots.
+    pub fn sizes(&self) -> impl Iterator<Item = usize> {
+        self.parents
+            .iter()
+            .zip(&self.sizes)
+            .enumerate()
+            .filter_map(|(i, (parent, size))| (i == *parent).then_some(*size))
+    }
+
+    /// The members of every component, each in order, and ordered by their smallest member.
+    pub fn components(&self) -> impl Iterator<Item = Vec<usize>> + use<> {
+        let mut slots = vec![None; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.num_components);
+        for item in 0..self.len() {
+            let slot = slots[self.component_of(item)].get_or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[*slot].push(item);
+        }
+        components.into_iter()
+    }
+}
+
+/// Union-find over arbitrary hashable keys, which join the set the first time they are used.
+///
+/// ```
+/// use aoc_lib::union_find::KeyedDisjointSet;
+///
+/// let mut set = KeyedDisjointSet::new();
+/// let mut joined = Vec::new();
+/// for (a, b) in [("kh", "tc"), ("qp", "kh"), ("de", "cg"), ("tc", "qp")] {
+///     set.union_with(a, b, |merge| joined.push(merge.size));
+/// }
+/// assert_eq!(vec![2, 3, 2], joined);
+/// assert!(set.same(&"tc", &"qp"));
+/// assert_eq!(Some(3), set.size_of(&"kh"));
+/// assert_eq!(2, set.num_components());
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyedDisjointSet<K> {
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+    set: DisjointSet,
+}
+
+impl<K> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        KeyedDisjointSet {
+            indices: HashMap::new(),
+            keys: Vec::new(),
+            set: DisjointSet::new(0),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedDisjointSet<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Add a key in a component of its own, if it is not already in the set.
+    pub fn insert(&mut self, key: K) {
+        self.index(key);
+    }
+
+    fn index(&mut self, key: K) -> usize {
+        if let Some(index) = self.indices.get(&key) {
+            return *index;
+        }
+        let index = self.set.push();
+        self.keys.push(key.clone());
+        self.indices.insert(key, index);
+        index
+    }
+
+    #[must_use]
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// The key representing a key's component, or `None` if the key is not in the set.
+    #[must_use]
+    pub fn component_of(&self, key: &K) -> Option<&K> {
+        let index = self.indices.get(key)?;
+        Some(&self.keys[self.set.component_of(*index)])
+    }
+
+    /// Whether two keys are both in the set and in the same component.
+    #[must_use]
+    pub fn same(&self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(a), Some(b)) => self.set.same(*a, *b),
+            _ => false,
+        }
+    }
+
+    /// The number of keys in a key's component, or `None` if the key is not in the set.
+    #[must_use]
+    pub fn size_of(&self, key: &K) -> Option<usize> {
+        self.indices.get(key).map(|index| self.set.size_of(*index))
+    }
+
+    #[must_use]
+    pub fn num_components(&self) -> usize {
+        self.set.num_components()
+    }
+
+    /// Join the components of two keys, adding either if needed, and returning whether they were
+    /// separate.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        self.union_with(a, b, |_| {})
+    }
+
+    /// Join the components of two keys, adding either if needed, and calling `on_merge` if they
+    /// were separate. Returns whether they were.
+    pub fn union_with<F>(&mut self, a: K, b: K, on_merge: F) -> bool
+    where
+        F: FnOnce(Merge<&K>),
+    {
+        let (a, b) = (self.index(a), self.index(b));
+        let keys = &self.keys;
+        self.set.union_with(a, b, |merge| {
+            on_merge(Merge {
+                root: &keys[merge.root],
+                absorbed: &keys[merge.absorbed],
+                size: merge.size,
+            });
+        })
+    }
+
+    /// The keys of every component, each in the order they joined the set, and ordered by their
+    /// first key.
+    pub fn components(&self) -> impl Iterator<Item = Vec<&K>> {
+        self.set
+            .components()
+            .map(|members| members.into_iter().map(|index| &self.keys[index]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compresses_paths_and_tracks_sizes() {
+        let mut set = DisjointSet::new(6);
+        for (a, b) in [(0, 1), (2, 3), (1, 3), (4, 5)] {
+            set.union(a, b);
+        }
+        assert_eq!(2, set.num_components());
+        assert_eq!(4, set.size_of(0));
+        assert_eq!(2, set.size_of(5));
+        let mut sizes: Vec<_> = set.sizes().collect();
+        sizes.sort_unstable();
+        assert_eq!(vec![2, 4], sizes);
+
+        let root = set.find(0);
+        assert!((0..4).all(|item| set.component_of(item) == root));
+        assert!(!set.same(0, 4));
+        assert_eq!(6, set.push());
+        assert_eq!(3, set.num_components());
+    }
+
+    #[test]
+    fn reports_merges() {
+        let mut set = DisjointSet::new(4);
+        let mut merges = Vec::new();
+        set.union_with(0, 1, |merge| merges.push(merge));
+        set.union_with(2, 0, |merge| merges.push(merge));
+        set.union_with(1, 2, |merge| merges.push(merge));
+        assert_eq!(
+            vec![
+                Merge {
+                    root: 0,
+                    absorbed: 1,
+                    size: 2
+                },
+                Merge {
+                    root: 0,
+                    absorbed: 2,
+                    size: 3
+                },
+            ],
+            merges
+        );
+    }
+
+    #[test]
+    fn keys_join_when_first_used() {
+        let mut set = KeyedDisjointSet::new();
+        set.insert('a');
+        set.union('b', 'c');
+        set.union('d', 'b');
+        assert_eq!(4, set.len());
+        assert_eq!(set.component_of(&'d'), set.component_of(&'c'));
+        assert_eq!(None, set.component_of(&'z'));
+        assert!(!set.same(&'a', &'z'));
+        assert_eq!(
+            vec![vec![&'a'], vec![&'b', &'c', &'d']],
+            set.components().collect::<Vec<_>>()
+        );
+    }
+}
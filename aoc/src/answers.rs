@@ -0,0 +1,145 @@
+use crate::error;
+use crate::html;
+use crate::workspace::answers_path;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io::ErrorKind;
+
+/// Accepted answers for a day, one line per solved part such as `part 1: 1234`.
+///
+/// Each answer is a star, so the file doubles as a record of which stars have been earned.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    parts: [Option<String>; 2],
+}
+
+impl Answers {
+    /// Load the answers recorded for a day, or no answers if none have been recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors, or an error if a line of the file is malformed.
+    pub fn load(year: u16, day: u8) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(answers_path(year, day)) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut answers = Self::default();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let (index, answer) = line
+                .split_once(':')
+                .and_then(|(part, answer)| {
+                    let index = match part.trim() {
+                        "part 1" => 0,
+                        "part 2" => 1,
+                        _ => return None,
+                    };
+                    Some((index, answer.trim()))
+                })
+                .ok_or_else(|| error(format!("Malformed answers line: {line}")))?;
+            answers.parts[index] = Some(answer.to_owned());
+        }
+        Ok(answers)
+    }
+
+    /// Write the answers into the day's directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O errors.
+    pub fn save(&self, year: u16, day: u8) -> Result<(), Box<dyn Error>> {
+        fs::write(answers_path(year, day), self.to_string())?;
+        Ok(())
+    }
+
+    /// Record the answers from the site, keeping any it did not show, returning whether anything
+    /// changed.
+    pub fn merge(&mut self, site: Answers) -> bool {
+        let mut changed = false;
+        for (ours, theirs) in self.parts.iter_mut().zip(site.parts) {
+            if theirs.is_some() && *ours != theirs {
+                *ours = theirs;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Number of stars earned, one per answered part.
+    pub fn stars(&self) -> usize {
+        self.parts.iter().flatten().count()
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, answer) in self.parts.iter().enumerate() {
+            if let Some(answer) = answer {
+                writeln!(f, "part {}: {answer}", i + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pull the accepted answers out of a puzzle page.
+///
+/// Once a part is solved the site shows "Your puzzle answer was" followed by the answer in code,
+/// after the part's description.
+pub fn extract(page: &str) -> Answers {
+    let root = html::parse(page);
+    let mut answers = Answers::default();
+    let found = root.find_all(|e| e.name == "p" && e.text().starts_with("Your puzzle answer was"));
+    for (slot, p) in answers.parts.iter_mut().zip(found) {
+        *slot = p
+            .find_all(|e| e.name == "code")
+            .first()
+            .map(|code| code.text().trim().to_owned());
+    }
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>One</p></article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Two</p></article>
+<p>Your puzzle answer was <code>abc,def</code>.</p>
+<p class=\"day-success\">Both parts of this puzzle are complete! They provide two gold stars: **</p>";
+
+    #[test]
+    fn extracts_answers() {
+        let answers = extract(PAGE);
+        assert_eq!("part 1: 1234\npart 2: abc,def\n", answers.to_string());
+        assert_eq!(2, answers.stars());
+
+        let unsolved = extract("<article class=\"day-desc\"><p>One</p></article>");
+        assert_eq!(0, unsolved.stars());
+    }
+
+    #[test]
+    fn round_trips() {
+        let answers = Answers::parse("part 2: 7\n\npart 1: 42\n").expect("Should parse");
+        assert_eq!("part 1: 42\npart 2: 7\n", answers.to_string());
+        Answers::parse("part 3: 1").expect_err("No part 3");
+        Answers::parse("1234").expect_err("No part");
+    }
+
+    #[test]
+    fn merges_site_answers() {
+        let mut answers = Answers::parse("part 2: 7\n").expect("Should parse");
+        let part_one = "<p>Your puzzle answer was <code>42</code>.</p>";
+        assert!(answers.merge(extract(part_one)));
+        assert_eq!("part 1: 42\npart 2: 7\n", answers.to_string());
+        assert!(!answers.merge(extract(part_one)));
+        assert!(answers.merge(extract(PAGE)));
+        assert_eq!("part 1: 1234\npart 2: abc,def\n", answers.to_string());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::error;
+use crate::input;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Session cookies are exactly 128 hex digits. SHA-512 digests are too, so a run that long only
+/// counts as a session when it is alone on its line or the line mentions a session.
+const SESSION_LEN: usize = 128;
+
+/// Git's mode for a submodule, which has no blob to read.
+const SUBMODULE_MODE: &str = "160000";
+
+/// A file as it is in the git index, rather than the working tree.
+#[derive(Debug)]
+pub struct StagedFile {
+    pub path: PathBuf,
+    pub content: Vec<u8>,
+}
+
+/// Every file in the git index, which is what has been committed plus anything staged since, and
+/// so what the next push can publish.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run or fails, such as outside a repository.
+pub fn staged_files() -> Result<Vec<StagedFile>, Box<dyn Error>> {
+    let index = git(&["ls-files", "-s", "-z"], None)?;
+    let entries = parse_index(&index)?;
+    let ids = entries
+        .iter()
+        .fold(String::new(), |ids, (id, _)| ids + id + "\n");
+    let blobs = parse_batch(&git(&["cat-file", "--batch"], Some(ids))?)?;
+    if blobs.len() != entries.len() {
+        return Err(error("git cat-file did not return every staged file."));
+    }
+    Ok(entries
+        .into_iter()
+        .zip(blobs)
+        .map(|((_, path), content)| StagedFile { path, content })
+        .collect())
+}
+
+/// Run git, feeding it some input, and return what it prints.
+fn git(args: &[&str], input: Option<String>) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut child = Command::new("git")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| error("git should take input."))?;
+    // Written from another thread so git never blocks on a full output pipe while we write.
+    let writer = thread::spawn(move || stdin.write_all(input.unwrap_or_default().as_bytes()));
+    let output = child.wait_with_output()?;
+    writer
+        .join()
+        .map_err(|_| error("Writing to git panicked."))??;
+    if !output.status.success() {
+        return Err(error(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+/// The object id and path of each blob in `git ls-files -s -z` output.
+fn parse_index(output: &[u8]) -> Result<Vec<(String, PathBuf)>, Box<dyn Error>> {
+    let mut entries = Vec::new();
+    for entry in output.split(|b| *b == 0).filter(|entry| !entry.is_empty()) {
+        let entry = String::from_utf8_lossy(entry);
+        let (stage, path) = entry
+            .split_once('\t')
+            .ok_or_else(|| error(format!("Malformed index entry: {entry}")))?;
+        let mut fields = stage.split(' ');
+        let (Some(mode), Some(id)) = (fields.next(), fields.next()) else {
+            return Err(error(format!("Malformed index entry: {entry}")));
+        };
+        if mode != SUBMODULE_MODE {
+            entries.push((id.to_owned(), PathBuf::from(path)));
+        }
+    }
+    Ok(entries)
+}
+
+/// The content of each object in `git cat-file --batch` output, in order.
+fn parse_batch(mut output: &[u8]) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+    let mut blobs = Vec::new();
+    while !output.is_empty() {
+        let header_end = output
+            .iter()
+            .position(|b| *b == b'\n')
+            .ok_or_else(|| error("Unterminated git cat-file header."))?;
+        let header = String::from_utf8_lossy(&output[..header_end]);
+        let size: usize = match header.split(' ').collect::<Vec<_>>()[..] {
+            [_, _, size] => size.parse()?,
+            _ => return Err(error(format!("Unexpected git cat-file header: {header}"))),
+        };
+        let start = header_end + 1;
+        let content = output
+            .get(start..start + size)
+            .ok_or_else(|| error("Truncated git cat-file output."))?;
+        blobs.push(content.to_vec());
+        // Each object is followed by a newline.
+        output = output.get(start + size + 1..).unwrap_or_default();
+    }
+    Ok(blobs)
+}
+
+/// Reasons a tracked file must not be published, if any.
+///
+/// `inputs` are the checksums of downloaded inputs, so copies are caught wherever they are.
+pub fn scan(path: &Path, content: &[u8], inputs: &HashSet<&str>) -> Vec<String> {
+    let mut problems = Vec::new();
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some("input.txt") => problems.push("puzzle input".to_owned()),
+        Some("puzzle.md") => problems.push("puzzle description".to_owned()),
+        _ => {}
+    }
+    if inputs.contains(input::checksum(content).as_str()) {
+        problems.push("copy of a downloaded puzzle input".to_owned());
+    }
+    let text = String::from_utf8_lossy(content);
+    for (number, line) in text.lines().enumerate() {
+        if has_session(line) {
+            problems.push(format!("session cookie on line {}", number + 1));
+        }
+    }
+    problems
+}
+
+fn has_session(line: &str) -> bool {
+    let mentions_session = line.to_ascii_lowercase().contains("session");
+    line.split(|c: char| !c.is_ascii_hexdigit())
+        .filter(|run| run.len() == SESSION_LEN)
+        .any(|run| mentions_session || line.trim() == run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_puzzle_files() {
+        let none = HashSet::new();
+        assert_eq!(
+            vec!["puzzle input"],
+            scan(Path::new("2025/day-1/input.txt"), b"1\n", &none)
+        );
+        assert_eq!(
+            vec!["puzzle description"],
+            scan(Path::new("2025/day-1/puzzle.md"), b"## Day 1\n", &none)
+        );
+        assert!(scan(Path::new("2025/day-1/input.txt.enc"), b"\x01\x02", &none).is_empty());
+    }
+
+    #[test]
+    fn flags_copied_inputs() {
+        let checksum = input::checksum(b"L68\nL30\n");
+        let inputs = HashSet::from([checksum.as_str()]);
+        assert_eq!(
+            vec!["copy of a downloaded puzzle input"],
+            scan(Path::new("notes/sample.txt"), b"L68\nL30\n", &inputs)
+        );
+        assert!(scan(Path::new("notes/sample.txt"), b"L68\n", &inputs).is_empty());
+    }
+
+    #[test]
+    fn flags_sessions() {
+        let session = format!("const SESSION: &str = \"{}\";\n", "a1".repeat(64));
+        let content = format!("fn main() {{}}\n{session}");
+        assert_eq!(
+            vec!["session cookie on line 2"],
+            scan(
+                Path::new("src/main.rs"),
+                content.as_bytes(),
+                &HashSet::new()
+            )
+        );
+        let checksum = format!("{}  2025/day-1/input.txt", "a1".repeat(32));
+        assert!(
+            scan(
+                Path::new("inputs.sha256"),
+                checksum.as_bytes(),
+                &HashSet::new()
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn tells_sessions_from_digests() {
+        let hex = "a1".repeat(64);
+        assert!(has_session(&hex));
+        assert!(has_session(&format!("Cookie: session={hex}")));
+        assert!(has_session(&format!("session = \"{hex}\"")));
+        assert!(!has_session(&format!("{hex}  2025/day-1/input.txt")));
+        assert!(!has_session(&format!("const DIGEST: &str = \"{hex}\";")));
+        assert!(!has_session(&format!("session = \"{hex}ff\"")));
+    }
+
+    #[test]
+    fn reads_git_output() {
+        let index =
+            b"100644 aaa 0\tsrc/main.rs\x00160000 bbb 0\tvendor/lib\x00100644 ccc 0\tinput.txt\0";
+        assert_eq!(
+            vec![
+                ("aaa".to_owned(), PathBuf::from("src/main.rs")),
+                ("ccc".to_owned(), PathBuf::from("input.txt"))
+            ],
+            parse_index(index).expect("Should parse index")
+        );
+        let batch = b"aaa blob 3\nfn\n\nccc blob 0\n\n";
+        assert_eq!(
+            vec![b"fn\n".to_vec(), Vec::new()],
+            parse_batch(batch).expect("Should parse blobs")
+        );
+        parse_batch(b"aaa blob 9\nfn\n").expect_err("Should reject truncated blobs");
+    }
+}
@@ -0,0 +1,176 @@
+use crate::error;
+use crate::input::{self, Manifest};
+use std::error::Error;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tar::{Archive, Builder, Header};
+
+/// Files of a day that are kept out of git but worth moving between machines.
+pub const DAY_FILES: [&str; 3] = ["input.txt", "answers.txt", "puzzle.md"];
+
+/// A file and its content, by its path relative to the workspace root.
+pub type Entry = (PathBuf, Vec<u8>);
+
+/// Checksums of every other file in a bundle, in the same format as the input manifest.
+const MANIFEST: &str = "MANIFEST.sha256";
+
+/// Write files into a tar archive, followed by a manifest of their checksums.
+///
+/// # Errors
+///
+/// Returns any I/O errors.
+pub fn export(writer: impl Write, files: &[Entry]) -> Result<(), Box<dyn Error>> {
+    let mut builder = Builder::new(writer);
+    let mut manifest = Manifest::default();
+    for (path, content) in files {
+        append(&mut builder, path, content)?;
+        manifest.insert(path, input::checksum(content));
+    }
+    append(
+        &mut builder,
+        Path::new(MANIFEST),
+        manifest.to_string().as_bytes(),
+    )?;
+    builder.into_inner()?.flush()?;
+    Ok(())
+}
+
+fn append(
+    builder: &mut Builder<impl Write>,
+    path: &Path,
+    content: &[u8],
+) -> Result<(), Box<dyn Error>> {
+    let mut header = Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, path, content)?;
+    Ok(())
+}
+
+/// Read the files from a bundle, checking each against the manifest.
+///
+/// # Errors
+///
+/// Returns an error if the archive has no manifest, a file is missing from it or does not match
+/// its checksum, or a path is not a day file within the workspace.
+pub fn import(reader: impl Read) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let mut files = Vec::new();
+    let mut manifest = None;
+    for entry in Archive::new(reader).entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        if path == Path::new(MANIFEST) {
+            manifest = Some(Manifest::parse(&String::from_utf8_lossy(&content))?);
+        } else if is_day_file(&path) {
+            files.push((path, content));
+        } else {
+            return Err(error(format!(
+                "Bundle contains {}, which is not a day's input, answers or puzzle.",
+                path.display()
+            )));
+        }
+    }
+    let manifest = manifest.ok_or_else(|| error("Bundle has no manifest."))?;
+    for (path, content) in &files {
+        match manifest.get(path) {
+            Some(expected) if expected == input::checksum(content) => {}
+            Some(_) => return Err(error(format!("{} is damaged.", path.display()))),
+            None => return Err(error(format!("{} is not in the manifest.", path.display()))),
+        }
+    }
+    if let Some(missing) = manifest
+        .paths()
+        .find(|path| files.iter().all(|(file, _)| file != path))
+    {
+        return Err(error(format!("{} is missing.", missing.display())));
+    }
+    Ok(files)
+}
+
+/// Whether a path is exactly `<year>/day-<n>/<file>` for one of the day files, so importing cannot
+/// write anywhere else.
+fn is_day_file(path: &Path) -> bool {
+    let parts: Vec<_> = path
+        .components()
+        .map(|component| match component {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect();
+    match parts.as_slice() {
+        [Some(year), Some(day), Some(file)] => {
+            year.parse::<u16>().is_ok()
+                && day
+                    .strip_prefix("day-")
+                    .is_some_and(|day| day.parse::<u8>().is_ok())
+                && DAY_FILES.contains(file)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files() -> Vec<Entry> {
+        vec![
+            (
+                PathBuf::from("2025/day-1/input.txt"),
+                b"L68\nL30\n".to_vec(),
+            ),
+            (
+                PathBuf::from("2025/day-1/answers.txt"),
+                b"part 1: 3\n".to_vec(),
+            ),
+            (
+                PathBuf::from("2024/day-2/puzzle.md"),
+                b"## Day 2\n".to_vec(),
+            ),
+        ]
+    }
+
+    fn bundle(files: &[Entry]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        export(&mut archive, files).expect("Should export");
+        archive
+    }
+
+    #[test]
+    fn round_trips() {
+        let archive = bundle(&files());
+        assert_eq!(files(), import(archive.as_slice()).expect("Should import"));
+    }
+
+    #[test]
+    fn rejects_damaged_bundles() {
+        let mut archive = bundle(&files());
+        let offset = archive
+            .windows(4)
+            .position(|window| window == b"L68\n")
+            .expect("Should contain the input");
+        archive[offset] = b'R';
+        import(archive.as_slice()).expect_err("Damaged input");
+
+        let mut unlisted = Vec::new();
+        let mut builder = Builder::new(&mut unlisted);
+        append(&mut builder, Path::new("2025/day-1/input.txt"), b"1\n").expect("Should append");
+        builder.finish().expect("Should finish");
+        drop(builder);
+        import(unlisted.as_slice()).expect_err("No manifest");
+    }
+
+    #[test]
+    fn only_accepts_day_files() {
+        assert!(is_day_file(Path::new("2025/day-12/input.txt")));
+        assert!(is_day_file(Path::new("2015/day-1/puzzle.md")));
+        assert!(!is_day_file(Path::new("2025/day-1/src/main.rs")));
+        assert!(!is_day_file(Path::new("../day-1/input.txt")));
+        assert!(!is_day_file(Path::new("/2025/day-1/input.txt")));
+        assert!(!is_day_file(Path::new("2025/day-x/input.txt")));
+        assert!(!is_day_file(Path::new("Cargo.toml")));
+    }
+}
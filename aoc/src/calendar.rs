@@ -0,0 +1,220 @@
+use crate::error;
+use std::error::Error;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The first event was held in 2015.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// Events ran for 25 days until 2024, and for 12 days from 2025.
+const SHORT_EVENTS_FROM: u16 = 2025;
+
+/// Puzzles unlock at midnight US Eastern time, which is always UTC-5 in December.
+const UNLOCK_OFFSET: Duration = Duration::from_hours(5);
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Source of the current time, so calendar logic can be tested at any instant.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+#[derive(Debug)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Number of days in a year's event, or `None` if there was no event that year.
+pub fn days_in_year(year: u16) -> Option<u8> {
+    if year < FIRST_YEAR {
+        None
+    } else if year < SHORT_EVENTS_FROM {
+        Some(25)
+    } else {
+        Some(12)
+    }
+}
+
+/// Number of days in a year's event.
+///
+/// # Errors
+///
+/// Returns an error if there was no event that year.
+pub fn event_length(year: u16) -> Result<u8, Box<dyn Error>> {
+    days_in_year(year).ok_or_else(|| {
+        error(format!(
+            "There was no event in {year}, the first was in {FIRST_YEAR}."
+        ))
+    })
+}
+
+/// Check a day is part of a year's event.
+///
+/// # Errors
+///
+/// Returns an error explaining why the year or day is not part of any event.
+pub fn validate_day(year: u16, day: u8) -> Result<(), Box<dyn Error>> {
+    let days = event_length(year)?;
+    if day == 0 || day > days {
+        return Err(error(format!(
+            "{year} day {day} does not exist, the {year} event has days 1 to {days}."
+        )));
+    }
+    Ok(())
+}
+
+/// Check a day is part of a year's event and has already unlocked.
+///
+/// # Errors
+///
+/// Returns an error if the day does not exist or is still locked.
+pub fn validate_unlocked(clock: &impl Clock, year: u16, day: u8) -> Result<(), Box<dyn Error>> {
+    validate_day(year, day)?;
+    match until_unlock(clock, year, day) {
+        Some(remaining) => Err(error(format!(
+            "{year} day {day} has not unlocked yet, it unlocks in {}.",
+            countdown(remaining)
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Days of a year's event that have unlocked.
+pub fn unlocked_days(clock: &impl Clock, year: u16) -> Vec<u8> {
+    (1..=days_in_year(year).unwrap_or(0))
+        .filter(|day| until_unlock(clock, year, *day).is_none())
+        .collect()
+}
+
+/// The instant a day's puzzle unlocks.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(i64::from(year), 12, i64::from(day));
+    // Events are all after 1970, so the day count is never negative.
+    let days = u64::try_from(days).unwrap_or_default();
+    UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY) + UNLOCK_OFFSET
+}
+
+/// Time left until a day's puzzle unlocks, or `None` if it already has.
+pub fn until_unlock(clock: &impl Clock, year: u16, day: u8) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(clock.now())
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// Format a duration as a countdown, such as `1d 02:03:04`.
+pub fn countdown(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    let days = seconds / SECONDS_PER_DAY;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/// Days since the Unix epoch of a date in the proleptic Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// A clock stopped at a fixed number of seconds after the epoch.
+    #[derive(Debug)]
+    pub struct FixedClock(pub u64);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> SystemTime {
+            UNIX_EPOCH + Duration::from_secs(self.0)
+        }
+    }
+
+    // 2025-12-01T05:00:00Z
+    const UNLOCK_2025_1: u64 = 1_764_565_200;
+
+    #[test]
+    fn civil_days() {
+        assert_eq!(0, days_from_civil(1970, 1, 1));
+        assert_eq!(20_423, days_from_civil(2025, 12, 1));
+        assert_eq!(11_016, days_from_civil(2000, 2, 29));
+    }
+
+    #[test]
+    fn unlocks_at_midnight_eastern() {
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(UNLOCK_2025_1),
+            unlock_time(2025, 1)
+        );
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_secs(UNLOCK_2025_1 + 11 * SECONDS_PER_DAY),
+            unlock_time(2025, 12)
+        );
+    }
+
+    #[test]
+    fn time_until_unlock() {
+        let before = FixedClock(UNLOCK_2025_1 - 90);
+        assert_eq!(
+            Some(Duration::from_secs(90)),
+            until_unlock(&before, 2025, 1)
+        );
+        assert_eq!(None, until_unlock(&FixedClock(UNLOCK_2025_1), 2025, 1));
+        assert_eq!(None, until_unlock(&FixedClock(UNLOCK_2025_1 + 1), 2025, 1));
+    }
+
+    #[test]
+    fn event_lengths() {
+        assert_eq!(None, days_in_year(2014));
+        assert_eq!(Some(25), days_in_year(2015));
+        assert_eq!(Some(25), days_in_year(2024));
+        assert_eq!(Some(12), days_in_year(2025));
+    }
+
+    #[test]
+    fn validates_days() {
+        validate_day(2015, 1).expect("First day of the first event");
+        validate_day(2024, 25).expect("Last day of a long event");
+        validate_day(2025, 12).expect("Last day of a short event");
+        validate_day(1999, 1).expect_err("No event");
+        validate_day(2024, 0).expect_err("No day 0");
+        validate_day(2024, 26).expect_err("Past the end of a long event");
+        validate_day(2025, 13).expect_err("Past the end of a short event");
+    }
+
+    #[test]
+    fn validates_unlocked_days() {
+        let clock = FixedClock(UNLOCK_2025_1 + 2 * SECONDS_PER_DAY);
+        validate_unlocked(&clock, 2024, 25).expect("Past event");
+        validate_unlocked(&clock, 2025, 3).expect("Unlocked today");
+        validate_unlocked(&clock, 2025, 4).expect_err("Unlocks tomorrow");
+        validate_unlocked(&clock, 2026, 1).expect_err("Future event");
+        assert_eq!(vec![1, 2, 3], unlocked_days(&clock, 2025));
+        assert_eq!(25, unlocked_days(&clock, 2024).len());
+        assert!(unlocked_days(&clock, 2026).is_empty());
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!("00:01:30", countdown(Duration::from_secs(90)));
+        assert_eq!("1d 02:03:04", countdown(Duration::from_secs(93_784)));
+    }
+}
@@ -0,0 +1,271 @@
+use crate::calendar::FIRST_YEAR;
+use crate::config::Config;
+use crate::error;
+use crate::throttle::Throttle;
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+use ureq::Agent;
+
+/// Attempts made at a request before giving up on rate limiting or server errors.
+const MAX_ATTEMPTS: u32 = 4;
+
+/// The longest `Retry-After` worth waiting for. Anything longer fails instead of hanging.
+const MAX_RETRY_AFTER: Duration = Duration::from_mins(5);
+
+/// Authenticated access to the site.
+#[derive(Debug)]
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    throttle: Throttle,
+}
+
+impl Client {
+    /// Create a client from the config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no session is configured.
+    pub fn new(config: &Config) -> Result<Self, Box<dyn Error>> {
+        let agent = Agent::config_builder()
+            .user_agent(config.user_agent())
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Ok(Client {
+            agent,
+            base_url: config.base_url().to_owned(),
+            session: config.session()?,
+            throttle: Throttle::new(config.request_interval()),
+        })
+    }
+
+    /// Fetch a page from the site, where the path is relative to the base URL.
+    ///
+    /// Every request waits on the throttle, and rate limited or server errors are retried with backoff.
+    ///
+    /// # Errors
+    ///
+    /// Returns any network errors, or an error for a non-success status.
+    pub fn get(&self, path: &str) -> Result<String, Box<dyn Error>> {
+        let mut attempt = 0;
+        loop {
+            self.throttle.wait()?;
+            let mut response = self
+                .agent
+                .get(self.url(path))
+                .header("Cookie", format!("session={}", self.session))
+                .call()?;
+            let status = response.status().as_u16();
+            if (200..300).contains(&status) {
+                // Assume responses are short enough that reading them into a string will be fine.
+                return Ok(response.body_mut().read_to_string()?);
+            }
+            attempt += 1;
+            if !(status == 429 || status >= 500) || attempt == MAX_ATTEMPTS {
+                return Err(error(format!(
+                    "Request for {path} failed with status {status}."
+                )));
+            }
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|value| value.to_str().ok()?.parse().ok())
+                .map(Duration::from_secs);
+            let delay = retry_delay(retry_after, self.throttle.backoff(attempt - 1))
+                .map_err(|e| error(format!("Request for {path} returned {status}. {e}")))?;
+            eprintln!("Request for {path} returned {status}, retrying in {delay:?}.");
+            thread::sleep(delay);
+        }
+    }
+
+    /// Full URL of a path on the site.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    /// Fetch the input for a day.
+    ///
+    /// # Errors
+    ///
+    /// Returns any network errors.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    /// Fetch the puzzle page for a day, which includes part two once part one is solved.
+    ///
+    /// # Errors
+    ///
+    /// Returns any network errors.
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        self.get(&puzzle_url_path(year, day))
+    }
+
+    /// Fetch the settings page, a small page which shows the logged in user.
+    ///
+    /// # Errors
+    ///
+    /// Returns any network errors.
+    pub fn settings(&self) -> Result<String, Box<dyn Error>> {
+        self.get(&format!("/{FIRST_YEAR}/settings"))
+    }
+
+    /// Fetch a private leaderboard as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns any network errors.
+    pub fn leaderboard(&self, year: u16, id: u64) -> Result<String, Box<dyn Error>> {
+        self.get(&format!("/{year}/leaderboard/private/view/{id}.json"))
+    }
+}
+
+/// How long to wait before retrying, as the server asks if it says, or else the backoff.
+fn retry_delay(
+    retry_after: Option<Duration>,
+    backoff: Duration,
+) -> Result<Duration, Box<dyn Error>> {
+    match retry_after {
+        Some(delay) if delay > MAX_RETRY_AFTER => Err(error(format!(
+            "The server asked to retry in {delay:?}, longer than the {MAX_RETRY_AFTER:?} worth waiting."
+        ))),
+        Some(delay) => Ok(delay),
+        None => Ok(backoff),
+    }
+}
+
+/// The path of a puzzle page, to join on to the base URL.
+pub fn puzzle_url_path(year: u16, day: u8) -> String {
+    format!("/{year}/day/{day}")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::env;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serve canned responses to a fixed number of requests, returning the raw requests received.
+    pub fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind a local port");
+        let url = format!(
+            "http://{}",
+            listener.local_addr().expect("Should have address")
+        );
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().expect("Should accept");
+                    let mut reader = BufReader::new(stream.try_clone().expect("Should clone"));
+                    let mut request = String::new();
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).expect("Should read request");
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        request.push_str(&line);
+                    }
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .expect("Should write response");
+                    request
+                })
+                .collect()
+        });
+        (url, handle)
+    }
+
+    /// A client for a mock server that does not wait between requests.
+    pub fn client(url: &str, name: &str) -> Client {
+        let config = format!("base_url = \"{url}\"\nuser_agent = \"me@example.com\"");
+        let config: Config = toml::from_str(&config).expect("Should parse");
+        let state = env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        Client {
+            agent: Agent::config_builder()
+                .user_agent(config.user_agent())
+                .http_status_as_error(false)
+                .build()
+                .into(),
+            base_url: config.base_url().to_owned(),
+            session: "cookie".to_owned(),
+            throttle: Throttle::with_state(state, Duration::ZERO, Duration::from_millis(1)),
+        }
+    }
+
+    #[test]
+    fn caps_retry_after() {
+        let backoff = Duration::from_secs(10);
+        assert_eq!(
+            backoff,
+            retry_delay(None, backoff).expect("Should back off")
+        );
+        assert_eq!(
+            Duration::from_secs(45),
+            retry_delay(Some(Duration::from_secs(45)), backoff).expect("Should wait")
+        );
+        let error = retry_delay(Some(Duration::from_secs(3_601)), backoff)
+            .expect_err("Should not wait an hour");
+        assert!(error.to_string().contains("3601s"));
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, server) = mock_server(vec![(200, "1\n2\n3\n")]);
+        let input = client(&url, "fetch").input(2025, 1).expect("Should fetch");
+        assert_eq!("1\n2\n3\n", input);
+
+        let requests = server.join().expect("Server should finish");
+        let request = requests[0].to_ascii_lowercase();
+        assert!(request.starts_with("get /2025/day/1/input http/1.1"));
+        assert!(request.contains("cookie: session=cookie"));
+        assert!(request.contains("user-agent: me@example.com"));
+    }
+
+    #[test]
+    fn fetches_settings() {
+        let (url, server) = mock_server(vec![(200, "<div class=\"user\">me</div>")]);
+        let page = client(&url, "settings").settings().expect("Should fetch");
+        assert_eq!("<div class=\"user\">me</div>", page);
+
+        let requests = server.join().expect("Server should finish");
+        assert!(requests[0].starts_with("GET /2015/settings HTTP/1.1"));
+    }
+
+    #[test]
+    fn reports_status_errors() {
+        let (url, server) = mock_server(vec![(404, "Not found")]);
+        client(&url, "status")
+            .input(2025, 1)
+            .expect_err("Should fail on 404");
+        assert_eq!(1, server.join().expect("Server should finish").len());
+    }
+
+    #[test]
+    fn retries_rate_limits_and_server_errors() {
+        let (url, server) = mock_server(vec![(429, ""), (503, ""), (200, "input\n")]);
+        let input = client(&url, "retry")
+            .input(2025, 1)
+            .expect("Should fetch after retrying");
+        assert_eq!("input\n", input);
+        assert_eq!(3, server.join().expect("Server should finish").len());
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let (url, server) = mock_server(vec![(500, ""); 4]);
+        client(&url, "give-up")
+            .input(2025, 1)
+            .expect_err("Should give up");
+        assert_eq!(4, server.join().expect("Server should finish").len());
+    }
+}
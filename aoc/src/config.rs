@@ -0,0 +1,197 @@
+use crate::error;
+use crate::vault::Key;
+use serde::Deserialize;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimum time between requests, as automated requests should be spaced well apart.
+const DEFAULT_REQUEST_INTERVAL: u64 = 5;
+
+/// The shortest interval that can be configured, so the throttle cannot be turned off.
+const MIN_REQUEST_INTERVAL: u64 = 1;
+
+/// Sent when no contact is configured, so requests can at least be traced back to this tool.
+const DEFAULT_USER_AGENT: &str = "github.com/jehiggs/advent-of-code";
+
+/// Settings for talking to the site, read from `~/.config/aoc/config.toml`.
+///
+/// ```toml
+/// session_file = "~/.config/aoc/session"
+/// user_agent = "github.com/jehiggs/advent-of-code by me@example.com"
+/// request_interval = 5
+/// input_key = "<64 hex digits from `aoc inputs key`>"
+/// ```
+///
+/// The `AOC_SESSION` and `AOC_INPUT_KEY` environment variables take precedence over any configured
+/// session or input key.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    session: Option<String>,
+    session_file: Option<PathBuf>,
+    base_url: Option<String>,
+    user_agent: Option<String>,
+    request_interval: Option<u64>,
+    input_key: Option<String>,
+}
+
+impl Config {
+    /// Load the config file, or the default config if there is no file.
+    ///
+    /// The file location can be overridden with the `AOC_CONFIG` environment variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors, or an error if the file is not valid config.
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let Some(path) = path() else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content)
+                .map_err(|e| error(format!("Invalid config {}: {e}", path.display()))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let config: Self = toml::from_str(content)?;
+        if config
+            .request_interval
+            .is_some_and(|interval| interval < MIN_REQUEST_INTERVAL)
+        {
+            return Err(error(format!(
+                "request_interval should be at least {MIN_REQUEST_INTERVAL} second(s)."
+            )));
+        }
+        Ok(config)
+    }
+
+    /// The session cookie, from the environment, the config or the session file in that order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no session is configured, or the session file cannot be read.
+    pub fn session(&self) -> Result<String, Box<dyn Error>> {
+        if let Ok(session) = env::var("AOC_SESSION") {
+            return Ok(session);
+        }
+        if let Some(session) = &self.session {
+            return Ok(session.clone());
+        }
+        if let Some(file) = &self.session_file {
+            let file = expand_home(file);
+            let session = fs::read_to_string(&file)
+                .map_err(|e| error(format!("Cannot read {}: {e}", file.display())))?;
+            return Ok(session.trim().to_owned());
+        }
+        Err(error(
+            "No session configured, set AOC_SESSION or add a session to the config file.",
+        ))
+    }
+
+    /// When the session was saved, from the file it is read from, or `None` if that is not known.
+    pub fn session_saved(&self) -> Option<SystemTime> {
+        if env::var_os("AOC_SESSION").is_some() {
+            return None;
+        }
+        let file = if self.session.is_some() {
+            path()?
+        } else {
+            expand_home(self.session_file.as_ref()?)
+        };
+        fs::metadata(file).ok()?.modified().ok()
+    }
+
+    /// The key for encrypting inputs, from the environment or the config in that order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no key is configured or the key is malformed.
+    pub fn input_key(&self) -> Result<Key, Box<dyn Error>> {
+        if let Ok(key) = env::var("AOC_INPUT_KEY") {
+            return Key::parse(&key);
+        }
+        match &self.input_key {
+            Some(key) => Key::parse(key),
+            None => Err(error(
+                "No input key configured, generate one with `aoc inputs key` and add it to the config file as input_key.",
+            )),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .map_or(DEFAULT_BASE_URL, |url| url.trim_end_matches('/'))
+    }
+
+    pub fn user_agent(&self) -> &str {
+        self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT)
+    }
+
+    pub fn request_interval(&self) -> Duration {
+        Duration::from_secs(self.request_interval.unwrap_or(DEFAULT_REQUEST_INTERVAL))
+    }
+}
+
+/// Location of the config file, following the XDG base directory convention.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("AOC_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join("aoc").join("config.toml"))
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults() {
+        let config = Config::parse("").expect("Empty config is valid");
+        assert_eq!("https://adventofcode.com", config.base_url());
+        assert_eq!("github.com/jehiggs/advent-of-code", config.user_agent());
+        assert_eq!(Duration::from_secs(5), config.request_interval());
+    }
+
+    #[test]
+    fn overrides() {
+        let config = Config::parse(
+            "base_url = \"http://127.0.0.1:8080/\"\nuser_agent = \"me@example.com\"\nsession = \"abc\"",
+        )
+        .expect("Should parse");
+        assert_eq!("http://127.0.0.1:8080", config.base_url());
+        assert_eq!("me@example.com", config.user_agent());
+        assert_eq!(Some("abc"), config.session.as_deref());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        Config::parse("sesion = \"abc\"").expect_err("Should reject typo");
+    }
+
+    #[test]
+    fn keeps_the_throttle_on() {
+        Config::parse("request_interval = 0").expect_err("Should reject no interval");
+        let config = Config::parse("request_interval = 1").expect("Should parse");
+        assert_eq!(Duration::from_secs(1), config.request_interval());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::error;
+use crate::html::{self, Node};
+use std::error::Error;
+use std::path::Path;
+use std::time::Duration;
+
+/// Sessions stop working around a month after logging in.
+const SESSION_LIFETIME: Duration = Duration::from_hours(30 * 24);
+
+/// Warn a few days before a session is likely to expire.
+const SESSION_WARNING: Duration = Duration::from_hours(25 * 24);
+
+/// Results of the checks, printed as they are made.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub passed: usize,
+    pub warnings: usize,
+    pub problems: usize,
+}
+
+impl Report {
+    pub fn ok(&mut self, message: &str) {
+        self.passed += 1;
+        println!("ok       {message}");
+    }
+
+    pub fn warning(&mut self, message: &str) {
+        self.warnings += 1;
+        println!("warning  {message}");
+    }
+
+    pub fn problem(&mut self, message: &str) {
+        self.problems += 1;
+        println!("problem  {message}");
+    }
+}
+
+/// Name of the logged in user from any page of the site, or `None` if the session is not valid.
+///
+/// The header shows the name followed by the star count, such as
+/// `<div class="user">name <span class="star-count">50*</span></div>`.
+pub fn user_name(page: &str) -> Option<String> {
+    let root = html::parse(page);
+    let user = *root
+        .find_all(|e| e.name == "div" && e.has_class("user"))
+        .first()?;
+    let name: String = user
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            Node::Text(text) => Some(text.as_str()),
+            Node::Element(_) => None,
+        })
+        .collect();
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+/// A hint about when the session will expire given how long ago it was saved, and whether it is a
+/// warning.
+pub fn session_age_hint(age: Duration) -> (bool, String) {
+    let days = age.as_secs() / (24 * 60 * 60);
+    let saved = format!("session was saved {days} day(s) ago");
+    if age >= SESSION_LIFETIME {
+        (
+            true,
+            format!("{saved}, it has probably expired, log in again and save the new cookie"),
+        )
+    } else if age >= SESSION_WARNING {
+        (
+            true,
+            format!("{saved}, sessions last around a month so it will expire soon"),
+        )
+    } else {
+        (false, saved)
+    }
+}
+
+/// Problems with the workspace manifest and day crates.
+///
+/// # Errors
+///
+/// Returns an error if the manifest is not valid TOML or has no members list.
+pub fn layout_problems(manifest: &str, day_dirs: &[&Path]) -> Result<Vec<String>, Box<dyn Error>> {
+    let manifest: toml::Table = toml::from_str(manifest)?;
+    let members: Vec<&str> = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(|members| members.as_array())
+        .ok_or_else(|| error("Workspace manifest has no members list."))?
+        .iter()
+        .filter_map(|member| member.as_str())
+        .collect();
+    let mut problems = Vec::new();
+    for member in &members {
+        let dir = Path::new(member);
+        if !dir.join("Cargo.toml").exists() {
+            problems.push(format!("workspace member {member} has no Cargo.toml"));
+        }
+    }
+    for dir in day_dirs {
+        let name = dir.to_string_lossy();
+        if !members.contains(&name.as_ref()) {
+            problems.push(format!("{name} is not a workspace member"));
+        }
+        if !dir.join("src").join("main.rs").exists() {
+            problems.push(format!("{name} has no src/main.rs"));
+        }
+    }
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_user_name() {
+        let page = "<header><div><h1 class=\"title-global\"><a href=\"/\">Advent of Code</a></h1>\
+            <div class=\"user\">Jane Doe <span class=\"star-count\">24*</span></div></div></header>";
+        assert_eq!(Some("Jane Doe".to_owned()), user_name(page));
+
+        let anonymous =
+            "<div class=\"user\">(anonymous user #1234) <span class=\"star-count\">2*</span></div>";
+        assert_eq!(
+            Some("(anonymous user #1234)".to_owned()),
+            user_name(anonymous)
+        );
+
+        let logged_out = "<header><a href=\"/2025/auth/login\">[Log In]</a></header>";
+        assert_eq!(None, user_name(logged_out));
+    }
+
+    #[test]
+    fn hints_at_expiry() {
+        let day = Duration::from_hours(24);
+        assert_eq!(
+            (false, "session was saved 3 day(s) ago".to_owned()),
+            session_age_hint(3 * day)
+        );
+        assert!(session_age_hint(26 * day).1.contains("expire soon"));
+        assert!(session_age_hint(40 * day).1.contains("probably expired"));
+    }
+
+    #[test]
+    fn checks_layout() {
+        let manifest = "[workspace]\nmembers = [\"no-such-member\"]";
+        let problems =
+            layout_problems(manifest, &[Path::new("no-such-day")]).expect("Should check");
+        assert_eq!(
+            vec![
+                "workspace member no-such-member has no Cargo.toml",
+                "no-such-day is not a workspace member",
+                "no-such-day has no src/main.rs",
+            ],
+            problems
+        );
+        layout_problems("[package]", &[]).expect_err("No members");
+    }
+}
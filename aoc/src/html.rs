@@ -0,0 +1,288 @@
+/// A minimal HTML tree, enough to pick apart the pages served by the site.
+///
+/// The site serves simple, well formed markup, so this does not attempt the error recovery a browser would.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+/// Elements which never have children or an end tag.
+const VOID_ELEMENTS: [&str; 8] = ["area", "br", "col", "hr", "img", "input", "link", "meta"];
+
+/// Elements whose content is not markup.
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attribute("class")
+            .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+    }
+
+    /// All descendant elements matching the predicate, in document order.
+    ///
+    /// Matching elements are not searched further, so nested matches are not returned.
+    pub fn find_all<P>(&self, predicate: P) -> Vec<&Element>
+    where
+        P: Fn(&Element) -> bool + Copy,
+    {
+        let mut found = Vec::new();
+        for child in &self.children {
+            if let Node::Element(element) = child {
+                if predicate(element) {
+                    found.push(element);
+                } else {
+                    found.extend(element.find_all(predicate));
+                }
+            }
+        }
+        found
+    }
+
+    /// Text content of the element and all its descendants.
+    pub fn text(&self) -> String {
+        self.children.iter().fold(String::new(), |mut text, child| {
+            match child {
+                Node::Text(t) => text.push_str(t),
+                Node::Element(element) => text.push_str(&element.text()),
+            }
+            text
+        })
+    }
+}
+
+/// Parse a document, returning a root element holding the top level nodes.
+pub fn parse(html: &str) -> Element {
+    let mut stack = vec![Element {
+        name: String::new(),
+        attributes: Vec::new(),
+        children: Vec::new(),
+    }];
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_node(&mut stack, Node::Text(decode_entities(rest)));
+            break;
+        };
+        if start > 0 {
+            push_node(&mut stack, Node::Text(decode_entities(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag.find('>').unwrap_or(tag.len());
+            close(&mut stack, &tag[..end].trim().to_ascii_lowercase());
+            rest = tag.get(end + 1..).unwrap_or("");
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let end = tag_end(rest);
+            let (element, self_closing) = parse_tag(&rest[1..end]);
+            rest = rest.get(end + 1..).unwrap_or("");
+            if RAW_TEXT_ELEMENTS.contains(&element.name.as_str()) {
+                // Raw text elements are never needed, so skip straight past their content.
+                let close_tag = format!("</{}", element.name);
+                let content_end = rest.to_ascii_lowercase().find(&close_tag);
+                rest = content_end.map_or("", |i| {
+                    let after = &rest[i..];
+                    after.find('>').map_or("", |j| &after[j + 1..])
+                });
+            } else if self_closing || VOID_ELEMENTS.contains(&element.name.as_str()) {
+                push_node(&mut stack, Node::Element(element));
+            } else {
+                stack.push(element);
+            }
+        } else {
+            // A lone '<' in text.
+            push_node(&mut stack, Node::Text("<".to_owned()));
+            rest = &rest[1..];
+        }
+    }
+    while stack.len() > 1 {
+        pop(&mut stack);
+    }
+    stack
+        .pop()
+        .unwrap_or_else(|| unreachable!("Root is never popped"))
+}
+
+fn push_node(stack: &mut [Element], node: Node) {
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(node);
+    }
+}
+
+fn pop(stack: &mut Vec<Element>) {
+    if let Some(element) = stack.pop() {
+        push_node(stack, Node::Element(element));
+    }
+}
+
+/// Close the innermost open element with the given name, along with anything left open inside it.
+fn close(stack: &mut Vec<Element>, name: &str) {
+    if let Some(index) = stack.iter().skip(1).rposition(|e| e.name == name) {
+        while stack.len() > index + 1 {
+            pop(stack);
+        }
+    }
+}
+
+/// Index of the '>' ending the tag at the start of the input, skipping any inside quoted attributes.
+fn tag_end(tag: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return i,
+            _ => {}
+        }
+    }
+    tag.len()
+}
+
+fn parse_tag(tag: &str) -> (Element, bool) {
+    let self_closing = tag.ends_with('/');
+    let tag = tag.trim_end_matches('/');
+    let name_end = tag.find(|c: char| c.is_whitespace()).unwrap_or(tag.len());
+    let mut attributes = Vec::new();
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, remaining) = if let Some(q @ ('"' | '\'')) = after.chars().next() {
+                let end = after[1..].find(q).map_or(after.len(), |i| i + 1);
+                (&after[1..end], after.get(end + 1..).unwrap_or(""))
+            } else {
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            };
+            rest = remaining.trim_start();
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+        attributes.push((key, value));
+    }
+    let element = Element {
+        name: tag[..name_end].to_ascii_lowercase(),
+        attributes,
+        children: Vec::new(),
+    };
+    (element, self_closing)
+}
+
+/// Replace character references with the characters they stand for.
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((entity(&rest[1..end])?, end)));
+        if let Some((c, end)) = entity {
+            decoded.push(c);
+            rest = &rest[end + 1..];
+        } else {
+            decoded.push('&');
+            rest = &rest[1..];
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_elements() {
+        let root = parse(
+            "<!DOCTYPE html><html><body><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>A <em>b</em><br>c</p></article></body></html>",
+        );
+        let articles = root.find_all(|e| e.name == "article");
+        assert_eq!(1, articles.len());
+        assert!(articles[0].has_class("day-desc"));
+        assert_eq!("--- Day 1 ---A bc", articles[0].text());
+        let paragraph = articles[0].find_all(|e| e.name == "p");
+        assert_eq!(4, paragraph[0].children.len());
+    }
+
+    #[test]
+    fn parses_attributes() {
+        let root = parse("<a href=\"/2025/about\" target=_blank title='x > y' hidden>link</a>");
+        let link = root.find_all(|e| e.name == "a")[0];
+        assert_eq!(Some("/2025/about"), link.attribute("href"));
+        assert_eq!(Some("_blank"), link.attribute("target"));
+        assert_eq!(Some("x > y"), link.attribute("title"));
+        assert_eq!(Some(""), link.attribute("hidden"));
+        assert_eq!("link", link.text());
+    }
+
+    #[test]
+    fn skips_comments_and_scripts() {
+        let root = parse("<p>a<!-- <p>b</p> -->c</p><script>if (a < b) {}</script>");
+        assert_eq!("ac", root.text());
+    }
+
+    #[test]
+    fn closes_unclosed_elements() {
+        let root = parse("<ul><li>a<li>b</ul><p>c");
+        assert_eq!("abc", root.text());
+        assert_eq!(2, root.children.len());
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            "a < b && c > d",
+            decode_entities("a &lt; b &amp;&amp; c &gt; d")
+        );
+        assert_eq!("'\"A", decode_entities("&#39;&quot;&#x41;"));
+        assert_eq!("a & b;", decode_entities("a & b;"));
+    }
+}
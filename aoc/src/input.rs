@@ -0,0 +1,210 @@
+use crate::error;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Manifest of input checksums, in the same format as `sha256sum` so it can be checked by hand.
+pub const MANIFEST: &str = "./.aoc/inputs.sha256";
+
+/// Messages the site returns in place of an input when the request is not allowed.
+const SITE_MESSAGES: [&str; 2] = [
+    "Puzzle inputs differ by user",
+    "Please don't repeatedly request this endpoint before it unlocks",
+];
+
+/// Tags that never appear in a puzzle input but always appear in an HTML page.
+const HTML_MARKERS: [&str; 4] = ["<!doctype", "<html", "<head", "<body"];
+
+/// Check a downloaded body looks like a puzzle input.
+///
+/// Puzzle inputs are plain ASCII text ending in a newline, so anything else is rejected.
+///
+/// # Errors
+///
+/// Returns an error describing why the body is not a puzzle input.
+pub fn validate(input: &str) -> Result<(), Box<dyn Error>> {
+    if input.trim().is_empty() {
+        return Err(error("Input is empty."));
+    }
+    if SITE_MESSAGES.iter().any(|message| input.contains(message)) {
+        return Err(error(format!(
+            "Input is a message from the site: {}",
+            input.trim()
+        )));
+    }
+    let lowercase = input.to_ascii_lowercase();
+    if HTML_MARKERS.iter().any(|marker| lowercase.contains(marker)) {
+        return Err(error("Input is an HTML page, not a puzzle input."));
+    }
+    if let Some(position) = input
+        .bytes()
+        .position(|b| !(b.is_ascii_graphic() || matches!(b, b' ' | b'\t' | b'\r' | b'\n')))
+    {
+        return Err(error(format!(
+            "Input is not plain text, found byte {:#04x} at offset {position}.",
+            input.as_bytes()[position]
+        )));
+    }
+    if !input.ends_with('\n') {
+        return Err(error(
+            "Input does not end with a newline, it may be truncated.",
+        ));
+    }
+    Ok(())
+}
+
+/// Hex encoded SHA-256 of some content.
+pub fn checksum(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// Checksums of downloaded inputs, keyed by their path relative to the workspace root.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    entries: BTreeMap<PathBuf, String>,
+}
+
+impl Manifest {
+    /// Load the manifest from the workspace, or an empty manifest if none has been written yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors, or an error if a line of the manifest is malformed.
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(MANIFEST) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parse a manifest in `sha256sum` format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line is malformed.
+    pub fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let entries = content
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                line.split_once("  ")
+                    .map(|(hash, path)| (PathBuf::from(path), hash.to_owned()))
+                    .ok_or_else(|| error(format!("Malformed manifest line: {line}")))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Manifest { entries })
+    }
+
+    /// Write the manifest back to the workspace.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O errors.
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let path = Path::new(MANIFEST);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn insert(&mut self, path: impl Into<PathBuf>, checksum: String) {
+        self.entries.insert(path.into(), checksum);
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&str> {
+        self.entries.get(path).map(String::as_str)
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(PathBuf::as_path)
+    }
+
+    pub fn checksums(&self) -> impl Iterator<Item = &str> {
+        self.entries.values().map(String::as_str)
+    }
+
+    /// Check the input at the given path is valid and matches its recorded checksum.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is missing, invalid, unrecorded or has changed since download.
+    pub fn verify(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let expected = self
+            .get(path)
+            .ok_or_else(|| error("No checksum recorded, fetch the input again."))?;
+        let content = fs::read(path)?;
+        validate(&String::from_utf8_lossy(&content))?;
+        if checksum(&content) == expected {
+            Ok(())
+        } else {
+            Err(error(
+                "Checksum does not match, the input has changed since it was downloaded.",
+            ))
+        }
+    }
+}
+
+impl std::fmt::Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (path, hash) in &self.entries {
+            writeln!(f, "{hash}  {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_plain_input() {
+        validate("L68\nL30\nR48\n").expect("Should be valid");
+        validate("<{([\n").expect("Should be valid");
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(validate("").is_err());
+        assert!(validate("\n\n").is_err());
+        assert!(
+            validate("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n")
+                .is_err()
+        );
+        assert!(validate("<!DOCTYPE html>\n<html lang=\"en-us\">\n</html>\n").is_err());
+        assert!(validate("12\u{0}34\n").is_err());
+        assert!(validate("1,2,3\n4,5").is_err());
+    }
+
+    #[test]
+    fn checksum_is_sha256() {
+        assert_eq!(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            checksum(b"")
+        );
+    }
+
+    #[test]
+    fn manifest_round_trip() {
+        let mut manifest = Manifest::default();
+        manifest.insert("2025/day-2/input.txt", "bb".to_owned());
+        manifest.insert("2025/day-1/input.txt", "aa".to_owned());
+        let text = manifest.to_string();
+        assert_eq!("aa  2025/day-1/input.txt\nbb  2025/day-2/input.txt\n", text);
+        let parsed = Manifest::parse(&text).expect("Should parse");
+        assert_eq!(Some("aa"), parsed.get(Path::new("2025/day-1/input.txt")));
+        Manifest::parse("no separator").expect_err("Should be malformed");
+    }
+}
@@ -0,0 +1,290 @@
+use crate::calendar;
+use crate::error;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// The site asks that private leaderboards are fetched at most once every 15 minutes.
+const MAX_AGE: Duration = Duration::from_mins(15);
+
+const CACHE_DIR: &str = "./.aoc";
+
+/// A private leaderboard, as served by `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    local_score: u64,
+    #[serde(default)]
+    last_star_ts: u64,
+    #[serde(default)]
+    completion_day_level: HashMap<u8, HashMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: u64,
+}
+
+/// Where a leaderboard is cached between fetches.
+pub fn cache_path(year: u16, id: u64) -> PathBuf {
+    Path::new(CACHE_DIR).join(format!("leaderboard-{year}-{id}.json"))
+}
+
+/// A cached leaderboard, if it was fetched recently enough to use instead of fetching again.
+///
+/// # Errors
+///
+/// Returns any I/O errors other than the cache not existing.
+pub fn cached(path: &Path, now: SystemTime) -> Result<Option<String>, Box<dyn Error>> {
+    let modified = match fs::metadata(path) {
+        Ok(metadata) => metadata.modified()?,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if now.duration_since(modified).unwrap_or_default() >= MAX_AGE {
+        return Ok(None);
+    }
+    Ok(Some(fs::read_to_string(path)?))
+}
+
+/// Parse a private leaderboard.
+///
+/// # Errors
+///
+/// Returns an error if the body is not a leaderboard, which is what the site serves when the
+/// session cannot view it.
+pub fn parse(json: &str) -> Result<Leaderboard, Box<dyn Error>> {
+    serde_json::from_str(json).map_err(|e| {
+        error(format!(
+            "Response is not a leaderboard ({e}), check the id and that your session is a member."
+        ))
+    })
+}
+
+impl Leaderboard {
+    /// Members by local score, then by stars, then by who got their last star first.
+    fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|m| {
+            (
+                std::cmp::Reverse(m.local_score),
+                std::cmp::Reverse(m.stars),
+                m.last_star_ts,
+                m.id,
+            )
+        });
+        members
+    }
+
+    /// Render the leaderboard as a table with the stars of each day, `*` for both parts and `+`
+    /// for part one only.
+    pub fn render(&self, year: u16) -> String {
+        let days = calendar::days_in_year(year).unwrap_or(0);
+        let members = self.ranked();
+        let width = members
+            .iter()
+            .map(|m| m.local_score.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max("Score".len());
+        let mut out = String::new();
+        let indent = " ".repeat(6 + width + 2);
+        let tens: String = (1..=days)
+            .map(|day| {
+                if day < 10 {
+                    ' '
+                } else {
+                    char::from(b'0' + day / 10)
+                }
+            })
+            .collect();
+        let units: String = (1..=days).map(|day| char::from(b'0' + day % 10)).collect();
+        let _ = writeln!(out, "{indent}{}", tens.trim_end());
+        let _ = writeln!(out, "Rank  {:>width$}  {units}  Stars  Name", "Score");
+        for (rank, member) in ranks(&members) {
+            let stars: String = (1..=days)
+                .map(|day| match member.stars_on(day) {
+                    2 => '*',
+                    1 => '+',
+                    _ => '.',
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "{:>4}  {:>width$}  {stars}  {:>5}  {}",
+                format!("{rank})"),
+                member.local_score,
+                member.stars,
+                member.name()
+            );
+        }
+        out
+    }
+
+    /// Render the time each member took to get the stars of one day, fastest first.
+    pub fn render_day(&self, year: u16, day: u8) -> String {
+        let mut members = self.ranked();
+        // Both stars first, ordered by the second, then one star ordered by the first.
+        members.sort_by_key(|m| {
+            let first = m.star_time(year, day, 1);
+            let second = m.star_time(year, day, 2);
+            (second.is_none(), second, first.is_none(), first)
+        });
+        let mut out = String::new();
+        let _ = writeln!(out, "Rank  {:<12}  {:<12}  Name", "Part 1", "Part 2");
+        for (position, member) in members.iter().enumerate() {
+            let times = [1, 2].map(|part| {
+                member
+                    .star_time(year, day, part)
+                    .map_or_else(|| "-".to_owned(), calendar::countdown)
+            });
+            let rank = if member.stars_on(day) > 0 {
+                format!("{})", position + 1)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                out,
+                "{rank:>4}  {:<12}  {:<12}  {}",
+                times[0],
+                times[1],
+                member.name()
+            );
+        }
+        out
+    }
+}
+
+impl Member {
+    fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn stars_on(&self, day: u8) -> usize {
+        self.completion_day_level.get(&day).map_or(0, HashMap::len)
+    }
+
+    /// Time from the day unlocking to the member getting a star.
+    fn star_time(&self, year: u16, day: u8, part: u8) -> Option<Duration> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        let unlock = calendar::unlock_time(year, day);
+        (SystemTime::UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+            .duration_since(unlock)
+            .ok()
+    }
+}
+
+/// Number members in order, giving members with the same local score the same rank.
+fn ranks<'a>(members: &[&'a Member]) -> Vec<(usize, &'a Member)> {
+    let mut ranked: Vec<(usize, &Member)> = Vec::with_capacity(members.len());
+    for (position, member) in members.iter().enumerate() {
+        let rank = match ranked.last() {
+            Some((rank, last)) if last.local_score == member.local_score => *rank,
+            _ => position + 1,
+        };
+        ranked.push((rank, member));
+    }
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard-2025.json");
+
+    #[test]
+    fn parses_fixture() {
+        let leaderboard = parse(FIXTURE).expect("Should parse");
+        let names: Vec<_> = leaderboard.ranked().iter().map(|m| m.name()).collect();
+        assert_eq!(vec!["Alice", "(anonymous user #1002)", "Carol"], names);
+    }
+
+    #[test]
+    fn rejects_pages() {
+        parse("<!DOCTYPE html><html></html>").expect_err("Not JSON");
+        parse("{\"event\": \"2025\"}").expect_err("No members");
+    }
+
+    #[test]
+    fn times_stars_from_unlock() {
+        let leaderboard = parse(FIXTURE).expect("Should parse");
+        let alice = &leaderboard.members["1001"];
+        assert_eq!(Some(Duration::from_mins(5)), alice.star_time(2025, 1, 1));
+        assert_eq!(Some(Duration::from_mins(70)), alice.star_time(2025, 2, 2));
+        assert_eq!(None, alice.star_time(2025, 3, 2));
+        assert_eq!(2, alice.stars_on(2));
+        assert_eq!(1, alice.stars_on(3));
+        assert_eq!(0, alice.stars_on(4));
+    }
+
+    #[test]
+    fn renders_table() {
+        let leaderboard = parse(FIXTURE).expect("Should parse");
+        assert_eq!(
+            "                      111
+Rank  Score  123456789012  Stars  Name
+  1)     13  **+.........      5  Alice
+  2)     10  **..........      4  (anonymous user #1002)
+  3)      0  ............      0  Carol
+",
+            leaderboard.render(2025)
+        );
+    }
+
+    #[test]
+    fn renders_day() {
+        let leaderboard = parse(FIXTURE).expect("Should parse");
+        assert_eq!(
+            "Rank  Part 1        Part 2        Name
+  1)  00:10:00      01:10:00      Alice
+  2)  00:03:20      02:20:00      (anonymous user #1002)
+      -             -             Carol
+",
+            leaderboard.render_day(2025, 2)
+        );
+    }
+
+    #[test]
+    fn shares_ranks_on_ties() {
+        let json = r#"{"members": {
+            "1": {"id": 1, "name": "a", "stars": 1, "local_score": 5},
+            "2": {"id": 2, "name": "b", "stars": 1, "local_score": 5},
+            "3": {"id": 3, "name": "c", "stars": 0, "local_score": 0}
+        }}"#;
+        let leaderboard = parse(json).expect("Should parse");
+        let ranks: Vec<_> = ranks(&leaderboard.ranked())
+            .into_iter()
+            .map(|(rank, _)| rank)
+            .collect();
+        assert_eq!(vec![1, 1, 3], ranks);
+    }
+
+    #[test]
+    fn uses_recent_cache() {
+        let path = env::temp_dir().join(format!("aoc-leaderboard-{}", std::process::id()));
+        let now = SystemTime::now();
+        assert_eq!(None, cached(&path, now).expect("Missing cache"));
+        fs::write(&path, FIXTURE).expect("Should write cache");
+        let fresh = cached(&path, now).expect("Should read cache");
+        assert_eq!(Some(FIXTURE), fresh.as_deref());
+        let stale = cached(&path, now + MAX_AGE).expect("Should read cache");
+        assert_eq!(None, stale);
+        fs::remove_file(&path).expect("Should remove cache");
+    }
+}
@@ -1,49 +1,647 @@
-use clap::Parser;
-use std::env;
+mod answers;
+mod audit;
+mod bundle;
+mod calendar;
+mod client;
+mod config;
+mod doctor;
+mod html;
+mod input;
+mod leaderboard;
+mod puzzle;
+mod samples;
+mod scaffold;
+mod status;
+mod throttle;
+mod vault;
+mod workspace;
+
+use answers::Answers;
+use calendar::SystemClock;
+use clap::{Parser, Subcommand};
+use client::Client;
+use config::Config;
+use input::Manifest;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::{Debug, Display};
-use std::fs;
-use std::path::PathBuf;
-use ureq::get;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use vault::Key;
+use workspace::{day_dir, encrypted_input_path, input_path, puzzle_path, source_path};
 
 #[derive(Debug, Parser)]
 #[command(version, about)]
 struct Args {
-    year: u16,
-    day: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Check files committed or staged in git for puzzle inputs, puzzle descriptions and session cookies.
+    Audit,
+    /// Check the session, the workspace layout and which days are missing inputs.
+    Doctor,
+    /// Download the input for a day into its directory, or for every day of a year if no day is given.
+    Fetch {
+        year: u16,
+        day: Option<u8>,
+        /// Download again even if the input already matches its checksum.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Manage encrypted copies of inputs, which can be committed unlike the inputs themselves.
+    Inputs {
+        #[command(subcommand)]
+        command: InputsCommand,
+    },
+    /// Show a private leaderboard, fetching it at most once every 15 minutes.
+    Leaderboard {
+        year: u16,
+        /// The id of the leaderboard, from the end of its URL.
+        id: u64,
+        /// Show the time each member took to get the stars of one day.
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// List the days of a year's event and when they unlock.
+    ListDays { year: u16 },
+    /// Create the crate for a day, add it to the workspace and download what is available for it.
+    New {
+        year: u16,
+        day: u8,
+        /// Template for the day, one of grid, lines, blocks or graph, or the path to a custom template.
+        #[arg(long, default_value = scaffold::DEFAULT_TEMPLATE)]
+        template: String,
+    },
+    /// Save the puzzle description for a day as Markdown, updating it once part two unlocks.
+    Puzzle {
+        year: u16,
+        day: u8,
+        /// Download again even if both parts have already been saved.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Fill the sample and expected answers of a day's tests from the examples on the puzzle page.
+    Samples { year: u16, day: u8 },
+    /// Show how far along each day of a year is, or of every year in the workspace if no year is given.
+    Status {
+        year: Option<u16>,
+        /// Skip running each day's tests, which can take a while.
+        #[arg(long)]
+        no_tests: bool,
+    },
+    /// Count down to a day unlocking, then download it and create its crate if needed.
+    Wait {
+        year: u16,
+        day: u8,
+        /// Template for the day if its crate does not exist yet.
+        #[arg(long, default_value = scaffold::DEFAULT_TEMPLATE)]
+        template: String,
+    },
+    /// Check downloaded inputs against the checksums recorded when they were fetched. An input
+    /// from before checksums were recorded has its checksum recorded instead.
+    Verify { year: Option<u16>, day: Option<u8> },
+}
+
+#[derive(Debug, Subcommand)]
+enum InputsCommand {
+    /// Print a new random key for encrypting inputs, to add to the config file.
+    Key,
+    /// Encrypt downloaded inputs into input.txt.enc next to them.
+    Lock { year: Option<u16>, day: Option<u8> },
+    /// Decrypt input.txt.enc files back into inputs.
+    Unlock { year: Option<u16>, day: Option<u8> },
+    /// Pack every day's input, answers and puzzle into an archive to move to another machine.
+    Export { file: PathBuf },
+    /// Unpack an archive made by export into the workspace.
+    Import {
+        file: PathBuf,
+        /// Replace files that differ from the ones in the archive.
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args = Args::parse();
-    let mut dir = PathBuf::from(format!("./{}/day-{}/", args.year, args.day));
-    if !dir.try_exists()? {
-        return Err(error("Directory for year and day does not exist."));
+    match args.command {
+        Command::Audit => audit(),
+        Command::Doctor => doctor(),
+        Command::Fetch { year, day, force } => fetch(year, day, force),
+        Command::Inputs { command } => match command {
+            InputsCommand::Key => {
+                println!("{}", Key::generate());
+                Ok(())
+            }
+            InputsCommand::Lock { year, day } => lock_inputs(year, day),
+            InputsCommand::Unlock { year, day } => unlock_inputs(year, day),
+            InputsCommand::Export { file } => export_inputs(&file),
+            InputsCommand::Import { file, force } => import_inputs(&file, force),
+        },
+        Command::Leaderboard { year, id, day } => show_leaderboard(year, id, day),
+        Command::ListDays { year } => list_days(year),
+        Command::New {
+            year,
+            day,
+            template,
+        } => new_day(year, day, &template),
+        Command::Puzzle { year, day, force } => save_puzzle(year, day, force),
+        Command::Samples { year, day } => fill_samples(year, day),
+        Command::Status { year, no_tests } => status(year, !no_tests),
+        Command::Verify { year, day } => verify(year, day),
+        Command::Wait {
+            year,
+            day,
+            template,
+        } => wait(year, day, &template),
+    }
+}
+
+fn audit() -> Result<(), Box<dyn Error>> {
+    let manifest = Manifest::load()?;
+    let inputs: HashSet<_> = manifest.checksums().collect();
+    let files = audit::staged_files()?;
+    let mut problems = 0;
+    for file in &files {
+        for problem in audit::scan(&file.path, &file.content, &inputs) {
+            problems += 1;
+            println!("{}: {problem}", file.path.display());
+        }
+    }
+    if problems > 0 {
+        return Err(error(format!(
+            "{problems} problem(s) found, remove them from git before pushing."
+        )));
+    }
+    println!("No problems in {} tracked file(s).", files.len());
+    Ok(())
+}
+
+fn doctor() -> Result<(), Box<dyn Error>> {
+    let mut report = doctor::Report::default();
+    let config = Config::load()?;
+    match config::path() {
+        Some(path) if path.try_exists()? => report.ok(&format!("config {}", path.display())),
+        Some(path) => report.warning(&format!("no config at {}, using defaults", path.display())),
+        None => report.warning("no config location, set AOC_CONFIG or HOME"),
+    }
+
+    match Client::new(&config) {
+        Ok(client) => match client.settings().map(|page| doctor::user_name(&page)) {
+            Ok(Some(name)) => report.ok(&format!("session logged in as {name}")),
+            Ok(None) => report.problem("session has expired, log in again and save the cookie"),
+            Err(e) => report.problem(&format!("session could not be checked: {e}")),
+        },
+        Err(e) => report.problem(&e.to_string()),
+    }
+    if let Some(age) = config
+        .session_saved()
+        .and_then(|saved| SystemTime::now().duration_since(saved).ok())
+    {
+        match doctor::session_age_hint(age) {
+            (true, hint) => report.warning(&hint),
+            (false, hint) => report.ok(&hint),
+        }
+    }
+
+    let days = workspace::select(None, None)?;
+    let dirs: Vec<_> = days.iter().map(|(y, d)| day_dir(*y, *d)).collect();
+    let dir_refs: Vec<_> = dirs.iter().map(PathBuf::as_path).collect();
+    let manifest = fs::read_to_string(scaffold::WORKSPACE_MANIFEST)?;
+    let problems = doctor::layout_problems(&manifest, &dir_refs)?;
+    for problem in &problems {
+        report.problem(problem);
     }
-    let session_cookie = env::var("AOC_SESSION")?;
-    let url = format!(
-        "https://adventofcode.com/{}/day/{}/input",
-        args.year, args.day
+    if !Path::new(scaffold::TEMPLATES).try_exists()? {
+        report.problem(&format!("no day templates in {}", scaffold::TEMPLATES));
+    } else if problems.is_empty() {
+        report.ok(&format!("workspace has {} day(s)", days.len()));
+    }
+
+    let mut missing = 0;
+    for (y, d) in days {
+        let path = input_path(y, d);
+        if calendar::until_unlock(&SystemClock, y, d).is_some() || path.try_exists()? {
+            continue;
+        }
+        missing += 1;
+        let fix = if encrypted_input_path(y, d).try_exists()? {
+            format!("aoc inputs unlock {y} {d}")
+        } else {
+            format!("aoc fetch {y} {d}")
+        };
+        report.warning(&format!("{} is missing, run {fix}", path.display()));
+    }
+    if missing == 0 {
+        report.ok("every unlocked day has an input");
+    }
+
+    println!(
+        "\n{} ok, {} warning(s), {} problem(s)",
+        report.passed, report.warnings, report.problems
     );
-    // Assume the input is short enough that reading it into a string will be fine.
-    let input = get(url)
-        .header("Cookie", format!("session={session_cookie}"))
-        .call()?
-        .body_mut()
-        .read_to_string()?;
+    if report.problems > 0 {
+        return Err(error("Fix the problems above before fetching."));
+    }
+    Ok(())
+}
+
+fn fetch(year: u16, day: Option<u8>, force: bool) -> Result<(), Box<dyn Error>> {
+    let days = if let Some(day) = day {
+        calendar::validate_unlocked(&SystemClock, year, day)?;
+        if !day_dir(year, day).try_exists()? {
+            return Err(error("Directory for year and day does not exist."));
+        }
+        vec![day]
+    } else {
+        calendar::event_length(year)?;
+        let unlocked = calendar::unlocked_days(&SystemClock, year);
+        workspace::days(year)?
+            .into_iter()
+            .filter(|d| unlocked.contains(d))
+            .collect()
+    };
+    let config = Config::load()?;
+    let mut client = None;
+    let mut manifest = Manifest::load()?;
+    for day_to_fetch in days {
+        let path = input_path(year, day_to_fetch);
+        // Inputs never change, so only download ones that are missing or damaged.
+        if !force && manifest.verify(&path).is_ok() {
+            println!("{}: already downloaded", path.display());
+            continue;
+        }
+        if !force
+            && encrypted_input_path(year, day_to_fetch).try_exists()?
+            && let Ok(key) = config.input_key()
+        {
+            unlock_input(&key, &mut manifest, year, day_to_fetch)?;
+            continue;
+        }
+        if client.is_none() {
+            client = Some(Client::new(&config)?);
+        }
+        let input = client
+            .as_ref()
+            .ok_or_else(|| error("Client should exist."))?
+            .input(year, day_to_fetch)?;
+        input::validate(&input)?;
+        manifest.insert(&path, input::checksum(input.as_bytes()));
+        fs::write(&path, input)?;
+        manifest.save()?;
+        println!("{}: downloaded", path.display());
+    }
+    Ok(())
+}
+
+fn lock_inputs(year: Option<u16>, day: Option<u8>) -> Result<(), Box<dyn Error>> {
+    let key = Config::load()?.input_key()?;
+    for (y, d) in workspace::select(year, day)? {
+        let path = input_path(y, d);
+        if !path.try_exists()? {
+            continue;
+        }
+        let input = fs::read(&path)?;
+        input::validate(&String::from_utf8_lossy(&input))?;
+        let locked = encrypted_input_path(y, d);
+        // Encryption is randomised, so only rewrite changed inputs to keep the history quiet.
+        let unchanged = fs::read(&locked).is_ok_and(|existing| {
+            key.decrypt(&path, &existing)
+                .is_ok_and(|decrypted| decrypted == input)
+        });
+        if unchanged {
+            println!("{}: unchanged", locked.display());
+            continue;
+        }
+        fs::write(&locked, key.encrypt(&path, &input)?)?;
+        println!("{}: locked", locked.display());
+    }
+    Ok(())
+}
+
+fn unlock_inputs(year: Option<u16>, day: Option<u8>) -> Result<(), Box<dyn Error>> {
+    let mut manifest = Manifest::load()?;
+    let mut to_unlock = Vec::new();
+    for (y, d) in workspace::select(year, day)? {
+        if !encrypted_input_path(y, d).try_exists()? {
+            continue;
+        }
+        let path = input_path(y, d);
+        if manifest.verify(&path).is_ok() {
+            println!("{}: already unlocked", path.display());
+        } else {
+            to_unlock.push((y, d));
+        }
+    }
+    // Only require a key when there is something to decrypt.
+    if to_unlock.is_empty() {
+        return Ok(());
+    }
+    let key = Config::load()?.input_key()?;
+    for (y, d) in to_unlock {
+        unlock_input(&key, &mut manifest, y, d)?;
+    }
+    Ok(())
+}
+
+fn unlock_input(
+    key: &Key,
+    manifest: &mut Manifest,
+    year: u16,
+    day: u8,
+) -> Result<(), Box<dyn Error>> {
+    let path = input_path(year, day);
+    let input = key.decrypt(&path, &fs::read(encrypted_input_path(year, day))?)?;
+    input::validate(&String::from_utf8_lossy(&input))?;
+    manifest.insert(&path, input::checksum(&input));
+    fs::write(&path, input)?;
+    manifest.save()?;
+    println!("{}: unlocked", path.display());
+    Ok(())
+}
+
+fn export_inputs(file: &Path) -> Result<(), Box<dyn Error>> {
+    let mut files = Vec::new();
+    for (y, d) in workspace::select(None, None)? {
+        for name in bundle::DAY_FILES {
+            let path = day_dir(y, d).join(name);
+            if path.try_exists()? {
+                let content = fs::read(&path)?;
+                files.push((path, content));
+            }
+        }
+    }
+    bundle::export(File::create(file)?, &files)?;
+    println!("{}: exported {} file(s)", file.display(), files.len());
+    Ok(())
+}
+
+fn import_inputs(file: &Path, force: bool) -> Result<(), Box<dyn Error>> {
+    let files = bundle::import(File::open(file)?)?;
+    // Check every input before writing anything, so a bad one cannot leave an import half done.
+    for (path, content) in &files {
+        if is_input(path) {
+            input::validate(&String::from_utf8_lossy(content))
+                .map_err(|e| error(format!("{}: {e}", path.display())))?;
+        }
+    }
+    let mut manifest = Manifest::load()?;
+    let written = import_files(&files, force, &mut manifest);
+    // Record checksums for whatever was written, even if a later file failed.
+    manifest.save()?;
+    written
+}
+
+fn is_input(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == "input.txt")
+}
+
+fn import_files(
+    files: &[bundle::Entry],
+    force: bool,
+    manifest: &mut Manifest,
+) -> Result<(), Box<dyn Error>> {
+    for (path, content) in files {
+        match fs::read(path) {
+            Ok(existing) if existing == *content => println!("{}: unchanged", path.display()),
+            Ok(_) if !force => {
+                println!("{}: differs, kept (use --force to replace)", path.display());
+                continue;
+            }
+            _ => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)?;
+                println!("{}: imported", path.display());
+            }
+        }
+        if is_input(path) {
+            manifest.insert(path, input::checksum(content));
+        }
+    }
+    Ok(())
+}
+
+fn show_leaderboard(year: u16, id: u64, day: Option<u8>) -> Result<(), Box<dyn Error>> {
+    calendar::event_length(year)?;
+    if let Some(day) = day {
+        calendar::validate_day(year, day)?;
+    }
+    let path = leaderboard::cache_path(year, id);
+    let json = if let Some(json) = leaderboard::cached(&path, SystemTime::now())? {
+        json
+    } else {
+        let client = Client::new(&Config::load()?)?;
+        let json = client.leaderboard(year, id)?;
+        // Only cache real leaderboards, so a bad id or session can be retried straight away.
+        leaderboard::parse(&json)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &json)?;
+        json
+    };
+    let board = leaderboard::parse(&json)?;
+    match day {
+        Some(day) => print!("{}", board.render_day(year, day)),
+        None => print!("{}", board.render(year)),
+    }
+    Ok(())
+}
+
+fn list_days(year: u16) -> Result<(), Box<dyn Error>> {
+    for day in 1..=calendar::event_length(year)? {
+        let status = match calendar::until_unlock(&SystemClock, year, day) {
+            Some(remaining) => format!("unlocks in {}", calendar::countdown(remaining)),
+            None => "unlocked".to_owned(),
+        };
+        println!("Day {day:>2}  {year}-12-{day:02}  {status}");
+    }
+    Ok(())
+}
+
+fn new_day(year: u16, day: u8, template: &str) -> Result<(), Box<dyn Error>> {
+    calendar::validate_day(year, day)?;
+    scaffold::new_day(year, day, template)?;
+    println!("{}: created", day_dir(year, day).display());
+    // The crate is still useful before the day unlocks, so a failed download is not an error.
+    if let Err(e) = download_day(year, day) {
+        eprintln!("Could not download the day yet: {e}");
+    }
+    Ok(())
+}
+
+fn download_day(year: u16, day: u8) -> Result<(), Box<dyn Error>> {
+    fetch(year, Some(day), false)?;
+    let client = Client::new(&Config::load()?)?;
+    let page = client.puzzle(year, day)?;
+    write_puzzle(&client, year, day, &page)?;
+    write_answers(year, day, &page)?;
+    write_samples(year, day, &page)
+}
+
+fn save_puzzle(year: u16, day: u8, force: bool) -> Result<(), Box<dyn Error>> {
+    calendar::validate_unlocked(&SystemClock, year, day)?;
+    if !day_dir(year, day).try_exists()? {
+        return Err(error("Directory for year and day does not exist."));
+    }
+    let path = puzzle_path(year, day);
+    if !force
+        && fs::read_to_string(&path).is_ok_and(|existing| existing.contains("--- Part Two ---"))
+        && Answers::load(year, day)?.stars() == 2
+    {
+        println!("{}: already has both parts", path.display());
+        return Ok(());
+    }
+    let client = Client::new(&Config::load()?)?;
+    let page = client.puzzle(year, day)?;
+    write_puzzle(&client, year, day, &page)?;
+    write_answers(year, day, &page)
+}
+
+fn write_puzzle(client: &Client, year: u16, day: u8, page: &str) -> Result<(), Box<dyn Error>> {
+    let path = puzzle_path(year, day);
+    let parts = puzzle::markdown(page, &client.url(&client::puzzle_url_path(year, day)));
+    if parts.is_empty() {
+        return Err(error("No puzzle description found, is the day unlocked?"));
+    }
+    fs::write(&path, parts.join("\n"))?;
+    println!("{}: saved {} part(s)", path.display(), parts.len());
+    Ok(())
+}
+
+/// Record the answers shown on the puzzle page for any parts already solved.
+fn write_answers(year: u16, day: u8, page: &str) -> Result<(), Box<dyn Error>> {
+    let mut answers = Answers::load(year, day)?;
+    if answers.merge(answers::extract(page)) {
+        answers.save(year, day)?;
+        println!(
+            "{}: recorded {} answer(s)",
+            workspace::answers_path(year, day).display(),
+            answers.stars()
+        );
+    }
+    Ok(())
+}
+
+fn fill_samples(year: u16, day: u8) -> Result<(), Box<dyn Error>> {
+    calendar::validate_unlocked(&SystemClock, year, day)?;
+    let client = Client::new(&Config::load()?)?;
+    write_samples(year, day, &client.puzzle(year, day)?)
+}
+
+fn write_samples(year: u16, day: u8, page: &str) -> Result<(), Box<dyn Error>> {
+    let path = source_path(year, day);
+    let source = fs::read_to_string(&path)?;
+    let examples = samples::extract(page);
+    let filled = samples::fill(&source, &examples);
+    if filled == source {
+        println!("{}: nothing to fill", path.display());
+    } else {
+        fs::write(&path, filled)?;
+        println!("{}: filled examples", path.display());
+    }
+    Ok(())
+}
+
+fn wait(year: u16, day: u8, template: &str) -> Result<(), Box<dyn Error>> {
+    // Give the site a moment after the unlock so the first request is not too early.
+    const GRACE: Duration = Duration::from_secs(2);
+    calendar::validate_day(year, day)?;
+    while let Some(remaining) = calendar::until_unlock(&SystemClock, year, day) {
+        print!(
+            "\r{year} day {day} unlocks in {}",
+            calendar::countdown(remaining)
+        );
+        io::stdout().flush()?;
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    println!("\r{year} day {day} has unlocked.");
+    thread::sleep(GRACE);
+    if day_dir(year, day).try_exists()? {
+        download_day(year, day)
+    } else {
+        new_day(year, day, template)
+    }
+}
 
-    // Assume all write paths are relative to the workspace root.
-    dir.push("input.txt");
-    fs::write(dir, input)?;
+fn status(year: Option<u16>, run_tests: bool) -> Result<(), Box<dyn Error>> {
+    let years = match year {
+        Some(year) => {
+            calendar::event_length(year)?;
+            vec![year]
+        }
+        None => workspace::years()?
+            .into_iter()
+            .filter(|y| calendar::days_in_year(*y).is_some())
+            .collect(),
+    };
+    for year_to_check in years {
+        let days = (1..=calendar::event_length(year_to_check)?)
+            .map(|day| status::check(&SystemClock, year_to_check, day, run_tests))
+            .collect::<Result<Vec<_>, _>>()?;
+        println!("{}", status::render(year_to_check, &days));
+    }
+    Ok(())
+}
+
+fn verify(year: Option<u16>, day: Option<u8>) -> Result<(), Box<dyn Error>> {
+    let mut manifest = Manifest::load()?;
+    let paths: Vec<_> = match (year, day) {
+        (Some(year), Some(day)) => vec![input_path(year, day)],
+        (Some(year), None) => manifest
+            .paths()
+            .filter(|path| path.starts_with(year.to_string()))
+            .map(PathBuf::from)
+            .collect(),
+        (None, _) => manifest.paths().map(PathBuf::from).collect(),
+    };
+
+    let mut failures = 0;
+    let mut recorded = false;
+    for path in &paths {
+        // Inputs downloaded before checksums were kept are trusted once, as long as they look valid.
+        if manifest.get(path).is_none() && path.try_exists()? {
+            let content = fs::read(path)?;
+            if let Err(e) = input::validate(&String::from_utf8_lossy(&content)) {
+                failures += 1;
+                println!("{}: FAILED ({e})", path.display());
+            } else {
+                manifest.insert(path, input::checksum(&content));
+                recorded = true;
+                println!("{}: RECORDED (no checksum yet)", path.display());
+            }
+            continue;
+        }
+        match manifest.verify(path) {
+            Ok(()) => println!("{}: OK", path.display()),
+            Err(e) => {
+                failures += 1;
+                println!("{}: FAILED ({e})", path.display());
+            }
+        }
+    }
+    if recorded {
+        manifest.save()?;
+    }
+    if failures > 0 {
+        return Err(error(format!("{failures} input(s) failed verification.")));
+    }
     Ok(())
 }
 
-fn error(msg: &'static str) -> Box<dyn Error> {
-    Box::new(AocError { reason: msg })
+fn error(msg: impl Into<String>) -> Box<dyn Error> {
+    Box::new(AocError { reason: msg.into() })
 }
 
-#[derive(Debug)]
 struct AocError {
-    reason: &'static str,
+    reason: String,
 }
 
 impl Display for AocError {
@@ -52,4 +650,11 @@ impl Display for AocError {
     }
 }
 
+// Errors returned from main are printed with Debug, so keep it as readable as Display.
+impl Debug for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
 impl Error for AocError {}
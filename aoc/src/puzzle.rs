@@ -0,0 +1,258 @@
+use crate::html::{self, Element, Node};
+use std::fmt::Write;
+
+/// Convert the puzzle descriptions on a day's page to Markdown, one entry per unlocked part.
+///
+/// Relative links are resolved against the URL of the page.
+pub fn markdown(page: &str, page_url: &str) -> Vec<String> {
+    let converter = Converter { page_url };
+    html::parse(page)
+        .find_all(|e| e.name == "article" && e.has_class("day-desc"))
+        .into_iter()
+        .map(|article| {
+            let mut out = String::new();
+            converter.blocks(article, &mut out);
+            out.truncate(out.trim_end().len());
+            out.push('\n');
+            out
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct Converter<'a> {
+    page_url: &'a str,
+}
+
+impl Converter<'_> {
+    fn blocks(&self, element: &Element, out: &mut String) {
+        for child in &element.children {
+            match child {
+                Node::Text(text) if text.trim().is_empty() => {}
+                Node::Text(text) => paragraph(out, &escape(&collapse_whitespace(text))),
+                Node::Element(e) => match e.name.as_str() {
+                    "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                        paragraph(out, &format!("## {}", self.inline(e).trim()));
+                    }
+                    "p" => paragraph(out, self.inline(e).trim()),
+                    "pre" => {
+                        let code = e.text();
+                        let newline = if code.ends_with('\n') { "" } else { "\n" };
+                        paragraph(out, &format!("```\n{code}{newline}```"));
+                    }
+                    "ul" | "ol" => {
+                        self.list(e, 0, out);
+                        out.push('\n');
+                    }
+                    _ => self.blocks(e, out),
+                },
+            }
+        }
+    }
+
+    fn list(&self, list: &Element, depth: usize, out: &mut String) {
+        let items = list.children.iter().filter_map(|child| match child {
+            Node::Element(e) if e.name == "li" => Some(e),
+            _ => None,
+        });
+        for (i, item) in items.enumerate() {
+            let marker = if list.name == "ol" {
+                format!("{}.", i + 1)
+            } else {
+                "-".to_owned()
+            };
+            let (nested, content): (Vec<_>, Vec<_>) = item.children.iter().partition(
+                |child| matches!(child, Node::Element(e) if e.name == "ul" || e.name == "ol"),
+            );
+            let text = self.inline_nodes(content.into_iter());
+            let _ = writeln!(out, "{}{marker} {}", "  ".repeat(depth), text.trim());
+            for child in nested {
+                if let Node::Element(e) = child {
+                    self.list(e, depth + 1, out);
+                }
+            }
+        }
+    }
+
+    fn inline(&self, element: &Element) -> String {
+        self.inline_nodes(element.children.iter())
+    }
+
+    fn inline_nodes<'n>(&self, nodes: impl Iterator<Item = &'n Node>) -> String {
+        nodes.fold(String::new(), |mut text, node| {
+            match node {
+                Node::Text(t) => text.push_str(&escape(&collapse_whitespace(t))),
+                Node::Element(e) => text.push_str(&self.inline_element(e)),
+            }
+            text
+        })
+    }
+
+    fn inline_element(&self, element: &Element) -> String {
+        match element.name.as_str() {
+            "em" | "i" => wrap(&self.inline(element), "*"),
+            "strong" | "b" => wrap(&self.inline(element), "**"),
+            "code" => {
+                let code = code_span(&collapse_whitespace(&element.text()));
+                // The site highlights important values, such as answers, by emphasising inside code.
+                if element.find_all(|e| e.name == "em").is_empty() {
+                    code
+                } else {
+                    format!("**{code}**")
+                }
+            }
+            "a" => {
+                let text = self.inline(element);
+                match element.attribute("href") {
+                    Some(href) => format!("[{}]({})", text.trim(), self.resolve(href)),
+                    None => text,
+                }
+            }
+            "br" => "\n".to_owned(),
+            _ => self.inline(element),
+        }
+    }
+
+    fn resolve(&self, href: &str) -> String {
+        if href.contains("://") || href.starts_with('#') {
+            return href.to_owned();
+        }
+        let (origin, path) = match self.page_url.find("://") {
+            Some(scheme_end) => {
+                let path_start = self.page_url[scheme_end + 3..]
+                    .find('/')
+                    .map_or(self.page_url.len(), |i| i + scheme_end + 3);
+                self.page_url.split_at(path_start)
+            }
+            None => (self.page_url, ""),
+        };
+        if href.starts_with('/') {
+            format!("{origin}{href}")
+        } else {
+            let dir = path.rfind('/').map_or("", |i| &path[..=i]);
+            format!("{origin}{dir}{href}")
+        }
+    }
+}
+
+fn paragraph(out: &mut String, text: &str) {
+    out.push_str(text);
+    out.push_str("\n\n");
+}
+
+/// Wrap text in a Markdown delimiter, keeping surrounding whitespace outside it so it still applies.
+fn wrap(text: &str, delimiter: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_owned();
+    }
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+    format!("{leading}{delimiter}{trimmed}{delimiter}{trailing}")
+}
+
+fn code_span(code: &str) -> String {
+    if code.contains('`') {
+        format!("`` {code} ``")
+    } else {
+        format!("`{code}`")
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut last_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_space {
+                collapsed.push(' ');
+            }
+            last_space = true;
+        } else {
+            collapsed.push(c);
+            last_space = false;
+        }
+    }
+    collapsed
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Secret Entrance ---</h2><p>The Elves have good news and <em>bad news</em>.</p>
+<p>For example, see <a href="/2025/about">the about page</a> or <a href="1/input">your input</a>:</p>
+<pre><code>L68
+L30
+</code></pre>
+<ul>
+<li>The dial starts by pointing at <code>50</code>.</li>
+<li>Then:<ul><li>nested &amp; escaped *</li></ul></li>
+</ul>
+<p>The password is <code><em>3</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+</main>
+</body>
+</html>"#;
+
+    const MARKDOWN: &str = "## --- Day 1: Secret Entrance ---
+
+The Elves have good news and *bad news*.
+
+For example, see [the about page](https://adventofcode.com/2025/about) or [your input](https://adventofcode.com/2025/day/1/input):
+
+```
+L68
+L30
+```
+
+- The dial starts by pointing at `50`.
+- Then:
+  - nested & escaped \\*
+
+The password is **`3`**.
+";
+
+    #[test]
+    fn converts_articles() {
+        let parts = markdown(PAGE, "https://adventofcode.com/2025/day/1");
+        assert_eq!(vec![MARKDOWN.to_owned()], parts);
+    }
+
+    #[test]
+    fn converts_both_parts() {
+        let page = "<article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>One</p></article>\
+            <p>Answer</p>\
+            <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Two</p></article>";
+        let parts = markdown(page, "https://adventofcode.com/2025/day/1");
+        assert_eq!(
+            vec![
+                "## --- Day 1 ---\n\nOne\n".to_owned(),
+                "## --- Part Two ---\n\nTwo\n".to_owned()
+            ],
+            parts
+        );
+    }
+
+    #[test]
+    fn wraps_inside_whitespace() {
+        assert_eq!(" *a b* ", wrap(" a b ", "*"));
+        assert_eq!(" ", wrap(" ", "*"));
+    }
+}
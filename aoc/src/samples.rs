@@ -0,0 +1,196 @@
+use crate::html::{self, Element, Node};
+
+/// The worked example from a puzzle page, and the answer it gives for each unlocked part.
+#[derive(Debug, Default, PartialEq)]
+pub struct Examples {
+    pub sample: Option<String>,
+    pub answers: Vec<Option<String>>,
+}
+
+/// Pull the example out of a puzzle page.
+///
+/// The sample is the first code block of part one. The site emphasises the example's answer as the
+/// last highlighted code of each part's description.
+pub fn extract(page: &str) -> Examples {
+    let root = html::parse(page);
+    let articles = root.find_all(|e| e.name == "article" && e.has_class("day-desc"));
+    let sample = articles.first().and_then(|article| {
+        article
+            .find_all(|e| e.name == "pre")
+            .first()
+            .map(|pre| pre.text().trim_end().to_owned())
+    });
+    let answers = articles
+        .iter()
+        .map(|article| {
+            article
+                .find_all(is_highlighted)
+                .last()
+                .map(|e| e.text().trim().to_owned())
+        })
+        .collect();
+    Examples { sample, answers }
+}
+
+/// Either `<code><em>42</em></code>` or `<em><code>42</code></em>`.
+fn is_highlighted(element: &Element) -> bool {
+    let inner = match element.name.as_str() {
+        "code" => "em",
+        "em" => "code",
+        _ => return false,
+    };
+    matches!(element.children.as_slice(), [Node::Element(e)] if e.name == inner)
+}
+
+/// Fill the placeholders left by the day template with the examples.
+///
+/// Only untouched placeholders are replaced, so anything already written by hand is kept.
+pub fn fill(source: &str, examples: &Examples) -> String {
+    let mut filled = source.to_owned();
+    if let Some(sample) = &examples.sample {
+        filled = filled.replacen(
+            "const SAMPLE: &str = \"\";",
+            &format!("const SAMPLE: &str = \"{}\";", escape(sample)),
+            1,
+        );
+    }
+    for (part, answer) in examples.answers.iter().enumerate() {
+        if let Some(answer) = answer {
+            filled = fill_answer(&filled, part + 1, answer);
+        }
+    }
+    filled
+}
+
+fn fill_answer(source: &str, part: usize, answer: &str) -> String {
+    const PLACEHOLDER: &str = "assert_eq!(0, result);";
+    let Some(test_start) = source.find(&format!("fn verify_part_{part}()")) else {
+        return source.to_owned();
+    };
+    let body = &source[test_start..];
+    let body_end = body[1..].find("fn ").map_or(body.len(), |i| i + 1);
+    match body[..body_end].find(PLACEHOLDER) {
+        Some(i) => {
+            let start = test_start + i;
+            format!(
+                "{}assert_eq!({}, result);{}",
+                &source[..start],
+                literal(answer),
+                &source[start + PLACEHOLDER.len()..]
+            )
+        }
+        None => source.to_owned(),
+    }
+}
+
+/// A Rust literal for an answer, grouping the digits of long numbers as clippy expects.
+fn literal(answer: &str) -> String {
+    let (sign, digits) = answer
+        .strip_prefix('-')
+        .map_or(("", answer), |digits| ("-", digits));
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return format!("\"{}\"", escape(answer));
+    }
+    if digits.len() <= 5 {
+        return answer.to_owned();
+    }
+    let first = digits.len() % 3;
+    let mut grouped = format!("{sign}{}", &digits[..first]);
+    for (i, chunk) in digits.as_bytes()[first..].chunks(3).enumerate() {
+        if first > 0 || i > 0 {
+            grouped.push('_');
+        }
+        grouped.push_str(&String::from_utf8_lossy(chunk));
+    }
+    grouped
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<article class=\"day-desc\"><h2>--- Day 5 ---</h2>
+<p>For example:</p>
+<pre><code>3-5
+10-14
+
+1
+\"5\"
+</code></pre>
+<p>Here, <code><em>2</em></code> are fresh and <em><code>3</code></em> are spoiled.</p>
+<p>So, <code><em>3</em></code> ingredient IDs are fresh.</p>
+<pre><code>not the sample</code></pre>
+</article>
+<p>Your puzzle answer was <code>123</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<p>Now there are <code><em>1227775554</em></code> fresh IDs.</p>
+</article>";
+
+    const TEMPLATE: &str = "#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = \"\";
+
+    #[test]
+    fn verify_part_1() {
+        let result = part_1(SAMPLE);
+        assert_eq!(0, result);
+    }
+
+    #[test]
+    fn verify_part_2() {
+        let result = part_2(SAMPLE);
+        assert_eq!(0, result);
+    }
+}
+";
+
+    #[test]
+    fn extracts_examples() {
+        let examples = extract(PAGE);
+        assert_eq!(Some("3-5\n10-14\n\n1\n\"5\""), examples.sample.as_deref());
+        assert_eq!(
+            vec![Some("3".to_owned()), Some("1227775554".to_owned())],
+            examples.answers
+        );
+    }
+
+    #[test]
+    fn fills_template() {
+        let filled = fill(TEMPLATE, &extract(PAGE));
+        assert!(filled.contains("const SAMPLE: &str = \"3-5\n10-14\n\n1\n\\\"5\\\"\";"));
+        assert!(filled.contains("part_1(SAMPLE);\n        assert_eq!(3, result);"));
+        assert!(filled.contains("part_2(SAMPLE);\n        assert_eq!(1_227_775_554, result);"));
+    }
+
+    #[test]
+    fn keeps_written_tests() {
+        let examples = Examples {
+            sample: None,
+            answers: vec![None, Some("5".to_owned())],
+        };
+        let filled = fill(TEMPLATE, &examples);
+        assert!(filled.contains("part_1(SAMPLE);\n        assert_eq!(0, result);"));
+        assert!(filled.contains("part_2(SAMPLE);\n        assert_eq!(5, result);"));
+        let refilled = fill(&filled, &extract(PAGE));
+        assert!(refilled.contains("part_2(SAMPLE);\n        assert_eq!(5, result);"));
+    }
+
+    #[test]
+    fn formats_literals() {
+        assert_eq!("42", literal("42"));
+        assert_eq!("25272", literal("25272"));
+        assert_eq!("123_456", literal("123456"));
+        assert_eq!("4_174_379_265", literal("4174379265"));
+        assert_eq!("\"abc,def\"", literal("abc,def"));
+        assert_eq!("-5", literal("-5"));
+        assert_eq!("-1_234_567", literal("-1234567"));
+        assert_eq!("\"-\"", literal("-"));
+        assert_eq!("\"-x\"", literal("-x"));
+    }
+}
@@ -0,0 +1,294 @@
+use crate::error;
+use crate::workspace::{day_dir, source_path};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{Decor, DocumentMut, RawString, Value};
+
+/// Templates for the `main.rs` of a new day, one per common shape of input.
+pub const TEMPLATES: &str = "./scripts/templates";
+
+pub const DEFAULT_TEMPLATE: &str = "lines";
+
+pub const WORKSPACE_MANIFEST: &str = "./Cargo.toml";
+
+/// Create the crate for a day from a template and add it to the workspace.
+///
+/// The template is either the name of one in `scripts/templates`, or a path to any other file.
+///
+/// # Errors
+///
+/// Returns an error if the day already exists or the template cannot be found, or any I/O errors.
+pub fn new_day(year: u16, day: u8, template: &str) -> Result<(), Box<dyn Error>> {
+    let dir = day_dir(year, day);
+    if dir.try_exists()? {
+        return Err(error(format!("{} already exists.", dir.display())));
+    }
+    let template = fs::read_to_string(template_path(template)?)?;
+    let manifest = fs::read_to_string(WORKSPACE_MANIFEST)?;
+    let manifest = add_member(&manifest, &dir.to_string_lossy())?;
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), crate_manifest(year, day))?;
+    fs::write(source_path(year, day), source(&template, year, day))?;
+    fs::write(WORKSPACE_MANIFEST, manifest)?;
+    Ok(())
+}
+
+fn template_path(template: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let builtin = Path::new(TEMPLATES).join(format!("{template}.rs"));
+    if builtin.try_exists()? {
+        return Ok(builtin);
+    }
+    let custom = PathBuf::from(template);
+    if custom.try_exists()? {
+        return Ok(custom);
+    }
+    let mut names: Vec<_> = fs::read_dir(TEMPLATES)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            Some(path.file_stem()?.to_string_lossy().into_owned())
+        })
+        .collect();
+    names.sort_unstable();
+    Err(error(format!(
+        "No template {template}, expected a path or one of: {}",
+        names.join(", ")
+    )))
+}
+
+fn crate_manifest(year: u16, day: u8) -> String {
+    format!(
+        "[package]
+name = \"day-{day}-{year}\"
+version = \"0.1.0\"
+edition = \"2024\"
+
+[lints]
+workspace = true
+
+[dependencies]
+aoc-lib = {{ workspace = true }}
+"
+    )
+}
+
+fn source(template: &str, year: u16, day: u8) -> String {
+    template.replacen(
+        "const INPUT: &str = \"\";",
+        &format!("const INPUT: &str = \"./{year}/day-{day}/input.txt\";"),
+        1,
+    )
+}
+
+/// Add a member to the workspace manifest at its place in natural order, leaving the layout and
+/// comments of the members list and the rest of the file untouched.
+fn add_member(manifest: &str, member: &str) -> Result<String, Box<dyn Error>> {
+    let mut document: DocumentMut = manifest.parse()?;
+    let members = document
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("members"))
+        .and_then(|members| members.as_array_mut())
+        .ok_or_else(|| error("Workspace manifest has no members list."))?;
+    if members.iter().any(|value| value.as_str() == Some(member)) {
+        return Ok(document.to_string());
+    }
+    let position = members
+        .iter()
+        .position(|value| {
+            value
+                .as_str()
+                .is_some_and(|name| natural_cmp(name, member).is_gt())
+        })
+        .unwrap_or(members.len());
+
+    let mut value = Value::from(member);
+    let previous = position.checked_sub(1).and_then(|p| members.get(p));
+    let indent = members
+        .get(position)
+        .or(previous)
+        .map(|neighbour| indent(neighbour.decor()))
+        .unwrap_or_default();
+    let last_suffix = previous
+        .filter(|_| position == members.len())
+        .map(|last| raw(last.decor().suffix()))
+        .filter(|suffix| !suffix.is_empty());
+    if let Some(suffix) = last_suffix {
+        // Without a trailing comma, the space before the closing bracket follows the last member.
+        if let Some(last) = members.get_mut(position - 1) {
+            last.decor_mut().set_suffix("");
+        }
+        let line = if suffix.contains('\n') { "\n" } else { " " };
+        value.decor_mut().set_prefix(format!("{line}{indent}"));
+        value.decor_mut().set_suffix(suffix);
+    } else {
+        // Whatever comes before the member or bracket that follows is split at the end of the
+        // previous line, so a comment there stays on that line and one above stays with the
+        // member below it.
+        let following = match members.get(position) {
+            Some(next) => raw(next.decor().prefix()),
+            None => members.trailing().as_str().unwrap_or_default().to_owned(),
+        };
+        let (prefix, rest) = match following.split_once('\n') {
+            Some((line_end, rest)) => (format!("{line_end}\n{indent}"), format!("\n{rest}")),
+            None if position == 0 => {
+                let rest = if members.is_empty() { "" } else { " " };
+                (following, rest.to_owned())
+            }
+            None => (" ".to_owned(), following),
+        };
+        value.decor_mut().set_prefix(prefix);
+        match members.get_mut(position) {
+            Some(next) => next.decor_mut().set_prefix(rest),
+            None => members.set_trailing(rest),
+        }
+    }
+    members.insert_formatted(position, value);
+    Ok(document.to_string())
+}
+
+fn raw(text: Option<&RawString>) -> String {
+    text.and_then(RawString::as_str)
+        .unwrap_or_default()
+        .to_owned()
+}
+
+/// The indentation of a member on its own line, or nothing for members on one line.
+fn indent(decor: &Decor) -> String {
+    let prefix = raw(decor.prefix());
+    prefix
+        .rfind('\n')
+        .map_or_else(String::new, |i| prefix[i + 1..].to_owned())
+}
+
+/// Compare strings with runs of digits compared by value, so `day-2` comes before `day-10`.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    natural_key(a).cmp(&natural_key(b))
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Segment<'a> {
+    Number(u64),
+    Text(&'a str),
+}
+
+fn natural_key(value: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = value;
+    while let Some(first) = rest.chars().next() {
+        let digit = first.is_ascii_digit();
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != digit)
+            .unwrap_or(rest.len());
+        let (segment, remaining) = rest.split_at(end);
+        segments.push(match segment.parse() {
+            Ok(number) if digit => Segment::Number(number),
+            _ => Segment::Text(segment),
+        });
+        rest = remaining;
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[workspace]
+resolver = \"3\"
+members = [\"2024/day-1\", \"2025/day-2\", \"2025/day-10\", \"aoc\", \"aoc-lib\"]
+
+# Shared dependencies.
+[workspace.dependencies]
+aoc-lib = { path = \"aoc-lib\" }
+";
+
+    #[test]
+    fn adds_member_in_order() {
+        let updated = add_member(MANIFEST, "2025/day-3").expect("Should add member");
+        assert_eq!(
+            "[workspace]
+resolver = \"3\"
+members = [\"2024/day-1\", \"2025/day-2\", \"2025/day-3\", \"2025/day-10\", \"aoc\", \"aoc-lib\"]
+
+# Shared dependencies.
+[workspace.dependencies]
+aoc-lib = { path = \"aoc-lib\" }
+",
+            updated
+        );
+    }
+
+    #[test]
+    fn adds_member_at_the_ends() {
+        let first = add_member(MANIFEST, "2023/day-1").expect("Should add member");
+        assert!(first.contains("members = [\"2023/day-1\", \"2024/day-1\", "));
+        let last = add_member(MANIFEST, "xtask").expect("Should add member");
+        assert!(last.contains(", \"aoc-lib\", \"xtask\"]\n"));
+        let only = add_member("[workspace]\nmembers = []\n", "aoc").expect("Should add member");
+        assert_eq!("[workspace]\nmembers = [\"aoc\"]\n", only);
+    }
+
+    #[test]
+    fn keeps_multi_line_layout() {
+        let manifest = "[workspace]
+members = [
+    # 2024
+    \"2024/day-1\",
+    # 2025
+    \"2025/day-2\", # the hard one
+    \"2025/day-10\",
+    \"aoc\",
+]
+";
+        let updated = add_member(manifest, "2025/day-3").expect("Should add member");
+        assert_eq!(
+            "[workspace]
+members = [
+    # 2024
+    \"2024/day-1\",
+    # 2025
+    \"2025/day-2\", # the hard one
+    \"2025/day-3\",
+    \"2025/day-10\",
+    \"aoc\",
+]
+",
+            updated
+        );
+        let first = add_member(manifest, "2023/day-1").expect("Should add member");
+        assert!(first.contains("[\n    \"2023/day-1\",\n    # 2024\n    \"2024/day-1\","));
+        let last = add_member("[workspace]\nmembers = [\n    \"aoc\"\n]\n", "aoc-lib")
+            .expect("Should add member");
+        assert_eq!(
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc-lib\"\n]\n",
+            last
+        );
+    }
+
+    #[test]
+    fn does_not_duplicate_members() {
+        let updated = add_member(MANIFEST, "aoc").expect("Should add member");
+        assert_eq!(1, updated.matches("\"aoc\"").count());
+    }
+
+    #[test]
+    fn natural_order() {
+        let mut names = vec!["2025/day-10", "aoc", "2025/day-2", "2024/day-25", "aoc-lib"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            vec!["2024/day-25", "2025/day-2", "2025/day-10", "aoc", "aoc-lib"],
+            names
+        );
+    }
+
+    #[test]
+    fn fills_input_path() {
+        let template = "const INPUT: &str = \"\";\nconst SAMPLE: &str = \"\";";
+        assert_eq!(
+            "const INPUT: &str = \"./2025/day-3/input.txt\";\nconst SAMPLE: &str = \"\";",
+            source(template, 2025, 3)
+        );
+    }
+}
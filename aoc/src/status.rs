@@ -0,0 +1,127 @@
+use crate::answers::Answers;
+use crate::calendar::{self, Clock};
+use crate::workspace::{day_dir, input_path};
+use std::error::Error;
+use std::fmt::Write;
+use std::process::{Command, Stdio};
+
+/// How far along a day is in the workspace.
+#[derive(Debug, Default, PartialEq)]
+pub struct DayStatus {
+    pub day: u8,
+    pub locked: bool,
+    pub crate_exists: bool,
+    pub input: bool,
+    /// Whether the day's tests pass, or `None` if they were not run.
+    pub tests: Option<bool>,
+    /// Stars earned, one per answer recorded.
+    pub stars: usize,
+}
+
+/// Check a day of the workspace, optionally running its tests.
+///
+/// # Errors
+///
+/// Returns any I/O errors, including failing to start `cargo`.
+pub fn check(
+    clock: &impl Clock,
+    year: u16,
+    day: u8,
+    run_tests: bool,
+) -> Result<DayStatus, Box<dyn Error>> {
+    let crate_exists = day_dir(year, day).try_exists()?;
+    let tests = if crate_exists && run_tests {
+        Some(tests_pass(year, day)?)
+    } else {
+        None
+    };
+    Ok(DayStatus {
+        day,
+        locked: calendar::until_unlock(clock, year, day).is_some(),
+        crate_exists,
+        input: input_path(year, day).try_exists()?,
+        tests,
+        stars: Answers::load(year, day)?.stars(),
+    })
+}
+
+fn tests_pass(year: u16, day: u8) -> Result<bool, Box<dyn Error>> {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--package", &format!("day-{day}-{year}")])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    Ok(status.success())
+}
+
+/// Render a year's days as a table, one row per day.
+pub fn render(year: u16, days: &[DayStatus]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{year:<7}  Crate  Input  Tests  Answers  Stars");
+    for status in days {
+        let tests = match status.tests {
+            Some(true) => "pass",
+            Some(false) => "FAIL",
+            None => "-",
+        };
+        let stars = if status.locked {
+            "locked".to_owned()
+        } else {
+            "*".repeat(status.stars)
+        };
+        let row = format!(
+            "Day {:>2}   {:<5}  {:<5}  {tests:<5}  {:<7}  {stars}",
+            status.day,
+            mark(status.crate_exists),
+            mark(status.input),
+            mark(status.stars > 0),
+        );
+        let _ = writeln!(out, "{}", row.trim_end());
+    }
+    let stars: usize = days.iter().map(|status| status.stars).sum();
+    let _ = writeln!(out, "{stars} of {} stars", days.len() * 2);
+    out
+}
+
+fn mark(present: bool) -> &'static str {
+    if present { "yes" } else { "-" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_days() {
+        let days = [
+            DayStatus {
+                day: 1,
+                crate_exists: true,
+                input: true,
+                tests: Some(true),
+                stars: 2,
+                ..DayStatus::default()
+            },
+            DayStatus {
+                day: 2,
+                crate_exists: true,
+                tests: Some(false),
+                ..DayStatus::default()
+            },
+            DayStatus {
+                day: 12,
+                locked: true,
+                ..DayStatus::default()
+            },
+        ];
+        assert_eq!(
+            "2025     Crate  Input  Tests  Answers  Stars
+Day  1   yes    yes    pass   yes      **
+Day  2   yes    -      FAIL   -
+Day 12   -      -      -      -        locked
+2 of 6 stars
+",
+            render(2025, &days)
+        );
+    }
+}
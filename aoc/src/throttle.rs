@@ -0,0 +1,128 @@
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// File holding the time of the last request, so the interval holds across invocations.
+pub const STATE: &str = "./.aoc/last-request";
+
+/// Delay before the first retry of a failed request, doubled on each further attempt.
+const BACKOFF: Duration = Duration::from_secs(10);
+
+/// Spaces requests to the site out by a minimum interval.
+#[derive(Debug)]
+pub struct Throttle {
+    state: PathBuf,
+    interval: Duration,
+    backoff: Duration,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Throttle {
+            state: PathBuf::from(STATE),
+            interval,
+            backoff: BACKOFF,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_state(state: PathBuf, interval: Duration, backoff: Duration) -> Self {
+        Throttle {
+            state,
+            interval,
+            backoff,
+        }
+    }
+
+    /// Block until a request is allowed, then record that one is being made.
+    ///
+    /// The state file is locked while waiting, so concurrent invocations queue up behind each other.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O errors from the state file.
+    pub fn wait(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = self.state.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.state)?;
+        file.lock()?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let last = content
+            .trim()
+            .parse()
+            .ok()
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        thread::sleep(delay(last, SystemTime::now(), self.interval));
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        write!(file, "{now}")?;
+        Ok(())
+    }
+
+    /// How long to back off before retrying after the given number of failed attempts.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff * 2u32.pow(attempt)
+    }
+}
+
+/// Time left until the next request is allowed.
+fn delay(last: Option<SystemTime>, now: SystemTime, interval: Duration) -> Duration {
+    last.and_then(|last| now.duration_since(last).ok())
+        .map_or(Duration::ZERO, |elapsed| interval.saturating_sub(elapsed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::time::Instant;
+
+    #[test]
+    fn delay_until_interval_elapsed() {
+        let interval = Duration::from_secs(5);
+        let now = SystemTime::now();
+        assert_eq!(Duration::ZERO, delay(None, now, interval));
+        assert_eq!(
+            Duration::from_secs(3),
+            delay(Some(now - Duration::from_secs(2)), now, interval)
+        );
+        assert_eq!(
+            Duration::ZERO,
+            delay(Some(now - Duration::from_mins(1)), now, interval)
+        );
+    }
+
+    #[test]
+    fn backoff_doubles() {
+        let throttle = Throttle::new(Duration::ZERO);
+        assert_eq!(Duration::from_secs(10), throttle.backoff(0));
+        assert_eq!(Duration::from_secs(40), throttle.backoff(2));
+    }
+
+    #[test]
+    fn interval_persists_between_throttles() {
+        let state = env::temp_dir().join(format!("aoc-throttle-{}", std::process::id()));
+        let interval = Duration::from_millis(200);
+        Throttle::with_state(state.clone(), interval, Duration::ZERO)
+            .wait()
+            .expect("Should record request");
+        let start = Instant::now();
+        Throttle::with_state(state.clone(), interval, Duration::ZERO)
+            .wait()
+            .expect("Should record request");
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        fs::remove_file(state).expect("Should clean up");
+    }
+}
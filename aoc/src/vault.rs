@@ -0,0 +1,138 @@
+use crate::error;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use std::error::Error;
+use std::fmt::Write;
+use std::path::Path;
+
+const NONCE_LEN: usize = 12;
+
+/// Symmetric key for inputs, so they can be committed without publishing them in plain text.
+pub struct Key(chacha20poly1305::Key);
+
+impl Key {
+    /// Parse a key from the 64 hex digits written by [`Key::generate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not 32 bytes of hex.
+    pub fn parse(hex: &str) -> Result<Self, Box<dyn Error>> {
+        let hex = hex.trim();
+        let invalid =
+            || error("Input key should be 64 hex digits, generate one with `aoc inputs key`.");
+        // Checked up front as `from_str_radix` would also accept a sign, such as `+f`.
+        if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        Ok(Key(*chacha20poly1305::Key::from_slice(&bytes)))
+    }
+
+    /// A new random key, as hex.
+    pub fn generate() -> String {
+        ChaCha20Poly1305::generate_key(&mut OsRng).iter().fold(
+            String::with_capacity(64),
+            |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            },
+        )
+    }
+
+    /// Encrypt an input, tying it to its path so encrypted files cannot be swapped between days.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encryption fails.
+    pub fn encrypt(&self, path: &Path, input: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = path.to_string_lossy();
+        let ciphertext = ChaCha20Poly1305::new(&self.0)
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: input,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| error("Could not encrypt input."))?;
+        Ok([nonce.as_slice(), &ciphertext].concat())
+    }
+
+    /// Decrypt an input encrypted for the same path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is wrong, or the encrypted file is damaged or for another path.
+    pub fn decrypt(&self, path: &Path, encrypted: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let failed = || {
+            error(format!(
+                "Could not decrypt {}, the key is wrong or the file is damaged.",
+                path.display()
+            ))
+        };
+        if encrypted.len() < NONCE_LEN {
+            return Err(failed());
+        }
+        let (nonce, ciphertext) = encrypted.split_at(NONCE_LEN);
+        let aad = path.to_string_lossy();
+        ChaCha20Poly1305::new(&self.0)
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| failed())
+    }
+}
+
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &[u8] = b"L68\nL30\n";
+
+    #[test]
+    fn round_trips() {
+        let key = Key::parse(&Key::generate()).expect("Generated key should parse");
+        let path = Path::new("2025/day-1/input.txt");
+        let encrypted = key.encrypt(path, INPUT).expect("Should encrypt");
+        assert_ne!(INPUT, &encrypted[NONCE_LEN..]);
+        assert_eq!(
+            INPUT,
+            key.decrypt(path, &encrypted).expect("Should decrypt")
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_key_or_path() {
+        let key = Key::parse(&"ab".repeat(32)).expect("Should parse");
+        let other = Key::parse(&"cd".repeat(32)).expect("Should parse");
+        let path = Path::new("2025/day-1/input.txt");
+        let encrypted = key.encrypt(path, INPUT).expect("Should encrypt");
+        other.decrypt(path, &encrypted).expect_err("Wrong key");
+        key.decrypt(Path::new("2025/day-2/input.txt"), &encrypted)
+            .expect_err("Wrong path");
+        key.decrypt(path, &encrypted[..4]).expect_err("Truncated");
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        Key::parse("abcd").expect_err("Too short");
+        Key::parse(&"zz".repeat(32)).expect_err("Not hex");
+        Key::parse(&"é".repeat(32)).expect_err("Not ASCII");
+        Key::parse(&"+f".repeat(32)).expect_err("Signed digits");
+    }
+}
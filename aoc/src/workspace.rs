@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+// Assume all paths are relative to the workspace root, which is where `cargo run` is invoked.
+
+/// Directory of a day's crate.
+pub fn day_dir(year: u16, day: u8) -> PathBuf {
+    PathBuf::from(format!("{year}/day-{day}"))
+}
+
+pub fn input_path(year: u16, day: u8) -> PathBuf {
+    day_dir(year, day).join("input.txt")
+}
+
+/// Encrypted copy of a day's input, which is safe to commit.
+pub fn encrypted_input_path(year: u16, day: u8) -> PathBuf {
+    day_dir(year, day).join("input.txt.enc")
+}
+
+pub fn source_path(year: u16, day: u8) -> PathBuf {
+    day_dir(year, day).join("src").join("main.rs")
+}
+
+pub fn puzzle_path(year: u16, day: u8) -> PathBuf {
+    day_dir(year, day).join("puzzle.md")
+}
+
+pub fn answers_path(year: u16, day: u8) -> PathBuf {
+    day_dir(year, day).join("answers.txt")
+}
+
+/// Years with a directory in the workspace, in order.
+///
+/// # Errors
+///
+/// Returns any I/O errors.
+pub fn years() -> Result<Vec<u16>, Box<dyn Error>> {
+    let mut years = Vec::new();
+    for entry in fs::read_dir(".")? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir()
+            && let Some(year) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse().ok())
+        {
+            years.push(year);
+        }
+    }
+    years.sort_unstable();
+    Ok(years)
+}
+
+/// Days of a year that have a crate in the workspace, in order.
+///
+/// # Errors
+///
+/// Returns any I/O errors other than the year directory not existing.
+pub fn days(year: u16) -> Result<Vec<u8>, Box<dyn Error>> {
+    let entries = match fs::read_dir(year.to_string()) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut days = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir()
+            && let Some(day) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("day-"))
+                .and_then(|day| day.parse().ok())
+        {
+            days.push(day);
+        }
+    }
+    days.sort_unstable();
+    Ok(days)
+}
+
+/// Days in the workspace, optionally only those of one year or one day of every year.
+///
+/// # Errors
+///
+/// Returns any I/O errors.
+pub fn select(year: Option<u16>, day: Option<u8>) -> Result<Vec<(u16, u8)>, Box<dyn Error>> {
+    let years = match year {
+        Some(year) => vec![year],
+        None => years()?,
+    };
+    let mut selected = Vec::new();
+    for y in years {
+        for d in days(y)? {
+            if day.is_none_or(|only| only == d) {
+                selected.push((y, d));
+            }
+        }
+    }
+    Ok(selected)
+}
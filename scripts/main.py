@@ -1,35 +1,28 @@
 # /// script
-# dependencies = [
-#   "toml",
-# ]
+# dependencies = []
 # ///
 
 import argparse
-import pathlib
 import subprocess
-import shutil
-import toml
 
 
 def new(args):
-    pathlib.Path(f"./{args.year}").mkdir(exist_ok=True)
-    run_cargo(
-        "new",
-        "--bin",
-        "--name",
-        f"day-{args.day}-{args.year}",
-        f"{args.year}/day-{args.day}",
-    )
-    shutil.copyfile("./scripts/template.rs", f"./{args.year}/day-{args.day}/src/main.rs")
-    toml_file = f"./{args.year}/day-{args.day}/Cargo.toml"
-    cargo = toml.load(toml_file)
-    cargo["dependencies"]["aoc-lib"] = {'workspace': True}
-    with open(toml_file, 'w') as f:
-        toml.dump(cargo, f)
-    run_cargo("run", "--release", "--bin", "aoc", "--", f"{args.year}", f"{args.day}")
+    output = run_cargo("run", "--release", "--bin", "aoc", "--", "new", f"{args.year}", f"{args.day}")
+    print(output.stdout.decode("utf-8"))
+    print(output.stderr.decode("utf-8"))
 
 
 def run(args):
+    unlock = run_cargo("run", "--release", "--bin", "aoc", "--", "inputs", "unlock", f"{args.year}", f"{args.day}")
+    if unlock.returncode != 0:
+        print(unlock.stdout.decode("utf-8"))
+        print(unlock.stderr.decode("utf-8"))
+        return
+    verify = run_cargo("run", "--release", "--bin", "aoc", "--", "verify", f"{args.year}", f"{args.day}")
+    if verify.returncode != 0:
+        print(verify.stdout.decode("utf-8"))
+        print(verify.stderr.decode("utf-8"))
+        return
     output = run_cargo("run", "--release", "--bin", f"day-{args.day}-{args.year}")
     print(output.stdout.decode("utf-8"))
 
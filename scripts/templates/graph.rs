@@ -1,4 +1,4 @@
-use aoc_lib::runner;
+use aoc_lib::{parse, runner};
 use std::error::Error;
 
 const INPUT: &str = "";
@@ -10,10 +10,12 @@ fn main() -> Result<(), Box<dyn Error>> {
 }
 
 fn part_1(input: &str) -> usize {
+    let _graph = parse::graph(input);
     0
 }
 
 fn part_2(input: &str) -> usize {
+    let _graph = parse::graph(input);
     0
 }
 